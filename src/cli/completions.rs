@@ -0,0 +1,308 @@
+use std::time::Duration;
+
+use dbus::blocking::Connection;
+use gnome_randr::DisplayConfig;
+use structopt::{clap::App, StructOpt};
+
+use super::profile::Profile;
+
+const BIN_NAME: &str = "gnome-randr";
+
+#[derive(Clone, Copy)]
+pub enum Shell {
+    Bash,
+    Zsh,
+    Fish,
+}
+
+impl std::str::FromStr for Shell {
+    type Err = std::fmt::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "bash" => Ok(Shell::Bash),
+            "zsh" => Ok(Shell::Zsh),
+            "fish" => Ok(Shell::Fish),
+            _ => Err(std::fmt::Error),
+        }
+    }
+}
+
+impl std::fmt::Display for Shell {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Shell::Bash => "bash",
+                Shell::Zsh => "zsh",
+                Shell::Fish => "fish",
+            }
+        )
+    }
+}
+
+#[derive(StructOpt)]
+pub struct CommandOptions {
+    #[structopt(
+        help = "One of 'bash', 'zsh' or 'fish'",
+        long_help = "One of 'bash', 'zsh' or 'fish'. Connectors, modes and profile names are completed from the live display configuration, e.g. with `source <(gnome-randr completions bash)`."
+    )]
+    pub shell: Shell,
+}
+
+// Each script asks `__complete` first, passing the words after the program name up to and including
+// the one being completed, and falls back to the static completions when it has nothing to offer.
+const BASH_DYNAMIC: &str = r#"
+_gnome_randr_dynamic() {
+    local candidates
+    candidates=$(gnome-randr __complete "${COMP_WORDS[@]:1:COMP_CWORD}" 2>/dev/null)
+    if [[ -n "$candidates" ]]; then
+        local IFS=$'\n'
+        COMPREPLY=($candidates)
+        return 0
+    fi
+    _gnome-randr "$@"
+}
+
+complete -F _gnome_randr_dynamic -o bashdefault -o default gnome-randr
+"#;
+
+const ZSH_DYNAMIC: &str = r#"
+_gnome_randr_dynamic() {
+    local -a candidates
+    candidates=(${(f)"$(gnome-randr __complete "${(@)words[2,CURRENT]}" 2>/dev/null)"})
+    if (( ${#candidates} )); then
+        compadd -a candidates
+    else
+        _gnome-randr "$@"
+    fi
+}
+
+compdef _gnome_randr_dynamic gnome-randr
+"#;
+
+const FISH_DYNAMIC: &str = r#"
+function __gnome_randr_complete
+    set -l tokens (commandline -opc)
+    set -e tokens[1]
+    gnome-randr __complete $tokens (commandline -ct) 2>/dev/null
+end
+
+complete -c gnome-randr -f -a '(__gnome_randr_complete)'
+"#;
+
+pub fn handle(opts: &CommandOptions, mut app: App) -> Result<(), Box<dyn std::error::Error>> {
+    let mut script = Vec::new();
+    app.gen_completions_to(
+        BIN_NAME,
+        match opts.shell {
+            Shell::Bash => structopt::clap::Shell::Bash,
+            Shell::Zsh => structopt::clap::Shell::Zsh,
+            Shell::Fish => structopt::clap::Shell::Fish,
+        },
+        &mut script,
+    );
+    let script = String::from_utf8(script)?;
+
+    match opts.shell {
+        Shell::Bash => {
+            // clap names the subcommands' cases after the binary with its dashes turned into
+            // underscores, but not the top-level one, so options after a subcommand never complete.
+            let underscored = BIN_NAME.replace('-', "__");
+            let script = script
+                .replace(
+                    &format!("cmd=\"{}\"", BIN_NAME),
+                    &format!("cmd=\"{}\"", underscored),
+                )
+                .replace(
+                    &format!("\n        {})\n", BIN_NAME),
+                    &format!("\n        {})\n", underscored),
+                );
+            print!("{}{}", script, BASH_DYNAMIC)
+        }
+        Shell::Zsh => {
+            // The generated script calls its completion function at the end, which only works when
+            // it is autoloaded, so leave that to compdef.
+            let call = format!("_{} \"$@\"", BIN_NAME);
+            let script: Vec<&str> = script.lines().filter(|line| *line != call).collect();
+            print!("{}\n{}", script.join("\n"), ZSH_DYNAMIC)
+        }
+        Shell::Fish => print!("{}{}", script, FISH_DYNAMIC),
+    }
+
+    Ok(())
+}
+
+// The `modify` options that take a value, so their values aren't mistaken for the connector.
+const MODIFY_VALUE_OPTIONS: &[&str] = &[
+    "-r",
+    "--rotate",
+    "-m",
+    "--mode",
+    "--rate",
+    "--scale",
+    "--pos",
+    "--left-of",
+    "--right-of",
+    "--above",
+    "--below",
+    "--align",
+    "--same-as",
+    "--confirm",
+    "--target-dpi",
+    "--layout-mode",
+];
+const MODIFY_CONNECTOR_OPTIONS: &[&str] = &[
+    "--left-of",
+    "--right-of",
+    "--above",
+    "--below",
+    "--same-as",
+    "--output",
+];
+const QUERY_VALUE_OPTIONS: &[&str] = &["--format", "--svg"];
+const PROFILE_NAME_COMMANDS: &[&str] = &["show", "apply", "delete"];
+
+enum Values {
+    Connectors,
+    Modes(Option<String>),
+    Scales(Option<String>),
+    Fixed(&'static [&'static str]),
+    Profiles,
+}
+
+/// The connector given to the current `modify` group, i.e. the positional argument or the value of
+/// the last --output.
+fn modify_connector(words: &[String]) -> Option<String> {
+    let mut connector = None;
+    let mut words = words.iter();
+    while let Some(word) = words.next() {
+        if word == "--output" {
+            connector = words.next().cloned();
+        } else if MODIFY_VALUE_OPTIONS.contains(&word.as_str()) {
+            words.next();
+        } else if !word.starts_with('-') && connector.is_none() {
+            connector = Some(word.clone());
+        }
+    }
+    connector
+}
+
+/// Works out what kind of value is being completed. `words` are the words before the current one,
+/// starting with the subcommand.
+fn values(words: &[String], current: &str) -> Option<Values> {
+    let (subcommand, args) = words.split_first()?;
+    let previous = args.last().map(String::as_str);
+
+    match subcommand.as_str() {
+        "query" => match previous {
+            Some(option) if QUERY_VALUE_OPTIONS.contains(&option) => None,
+            _ if current.starts_with('-') => None,
+            _ if args.iter().all(|arg| arg.starts_with('-')) => Some(Values::Connectors),
+            _ => None,
+        },
+        "modify" => {
+            let connector = modify_connector(args);
+            match previous {
+                Some(option) if MODIFY_CONNECTOR_OPTIONS.contains(&option) => {
+                    Some(Values::Connectors)
+                }
+                Some("-m") | Some("--mode") => Some(Values::Modes(connector)),
+                Some("--scale") => Some(Values::Scales(connector)),
+                Some("-r") | Some("--rotate") => {
+                    Some(Values::Fixed(&["normal", "left", "right", "inverted"]))
+                }
+                Some("--align") => Some(Values::Fixed(&["start", "center", "end"])),
+                Some("--layout-mode") => Some(Values::Fixed(&["logical", "physical"])),
+                Some(option) if MODIFY_VALUE_OPTIONS.contains(&option) => None,
+                _ if current.starts_with('-') || connector.is_some() => None,
+                _ => Some(Values::Connectors),
+            }
+        }
+        "profile" => match args {
+            [command] if PROFILE_NAME_COMMANDS.contains(&command.as_str()) => {
+                Some(Values::Profiles)
+            }
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+fn candidates(values: Values) -> Result<Vec<String>, Box<dyn std::error::Error>> {
+    let config = || -> Result<DisplayConfig, Box<dyn std::error::Error>> {
+        let conn = Connection::new_session()?;
+        let proxy = conn.with_proxy(
+            "org.gnome.Mutter.DisplayConfig",
+            "/org/gnome/Mutter/DisplayConfig",
+            Duration::from_millis(1000),
+        );
+        Ok(DisplayConfig::get_current_state(&proxy)?)
+    };
+
+    Ok(match values {
+        Values::Connectors => config()?
+            .monitors
+            .iter()
+            .map(|physical_monitor| physical_monitor.connector.clone())
+            .collect(),
+        Values::Modes(connector) => {
+            let mut candidates = Vec::new();
+            if let Some(connector) = connector {
+                let config = config()?;
+                if let Some(physical_monitor) = config.search_physical(&connector) {
+                    for ((width, height), modes) in physical_monitor.resolutions() {
+                        candidates.push(format!("{}x{}", width, height));
+                        candidates.extend(modes.iter().map(|mode| mode.id.clone()));
+                    }
+                }
+            }
+            candidates.extend(
+                ["preferred", "native", "highest-refresh"]
+                    .iter()
+                    .map(|keyword| keyword.to_string()),
+            );
+            candidates
+        }
+        Values::Scales(connector) => {
+            let config = config()?;
+            connector
+                .and_then(|connector| config.search_physical(&connector))
+                .and_then(|physical_monitor| physical_monitor.current_mode())
+                .map(|mode| {
+                    mode.supported_scales
+                        .iter()
+                        .map(|scale| scale.to_string())
+                        .collect()
+                })
+                .unwrap_or_default()
+        }
+        Values::Fixed(values) => values.iter().map(|value| value.to_string()).collect(),
+        Values::Profiles => Profile::list()?,
+    })
+}
+
+/// Prints the live values matching the word being completed, one per line. `words` are the words
+/// after the program name, ending with the one being completed. Prints nothing when the static
+/// completions should be used instead, or when the display server can't be reached.
+pub fn complete(words: &[String]) -> Result<(), Box<dyn std::error::Error>> {
+    let (current, words) = match words.split_last() {
+        Some(split) => split,
+        None => return Ok(()),
+    };
+
+    let candidates = match values(words, current).map(candidates) {
+        Some(Ok(candidates)) => candidates,
+        _ => return Ok(()),
+    };
+
+    for candidate in candidates
+        .iter()
+        .filter(|candidate| candidate.starts_with(current.as_str()))
+    {
+        println!("{}", candidate);
+    }
+
+    Ok(())
+}
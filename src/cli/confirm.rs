@@ -0,0 +1,269 @@
+use std::{
+    fs::File,
+    io::Read,
+    os::unix::{
+        fs::OpenOptionsExt,
+        io::{AsRawFd, RawFd},
+    },
+    path::PathBuf,
+    sync::atomic::{AtomicBool, Ordering},
+    time::{Duration, Instant},
+};
+
+use structopt::StructOpt;
+
+#[derive(StructOpt)]
+pub struct CommandOptions {}
+
+#[derive(Debug)]
+pub enum Error {
+    NothingPending,
+    AlreadyPending,
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self {
+            Error::NothingPending => {
+                write!(f, "fatal: no configuration is waiting to be confirmed.")
+            }
+            Error::AlreadyPending => write!(
+                f,
+                "fatal: another configuration is already waiting to be confirmed."
+            ),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+static CONFIRMED: AtomicBool = AtomicBool::new(false);
+static INTERRUPTED: AtomicBool = AtomicBool::new(false);
+
+extern "C" fn confirm_received(_: libc::c_int) {
+    CONFIRMED.store(true, Ordering::SeqCst);
+}
+
+extern "C" fn interrupt_received(_: libc::c_int) {
+    INTERRUPTED.store(true, Ordering::SeqCst);
+}
+
+/// How waiting for confirmation ended.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    Confirmed,
+    TimedOut,
+    /// The process was asked to stop, e.g. with Ctrl-C, and should revert before exiting.
+    Interrupted,
+}
+
+/// Where the process waiting for confirmation records its pid, so `confirm` knows who to signal.
+/// Only the user's runtime directory is used, since nobody else can write to it.
+fn pid_file() -> Option<PathBuf> {
+    std::env::var_os("XDG_RUNTIME_DIR")
+        .filter(|dir| !dir.is_empty())
+        .map(|dir| PathBuf::from(dir).join("gnome-randr-confirm.pid"))
+}
+
+/// Whether another process holds the lock on the file, i.e. is still waiting for confirmation.
+fn is_locked(fd: RawFd) -> bool {
+    if unsafe { libc::flock(fd, libc::LOCK_EX | libc::LOCK_NB) } == 0 {
+        unsafe { libc::flock(fd, libc::LOCK_UN) };
+        false
+    } else {
+        true
+    }
+}
+
+/// The pid file of a waiting process, locked for as long as it waits so `confirm` can tell it from
+/// one left behind by a process that died.
+struct PidFile {
+    path: PathBuf,
+    _file: File,
+}
+
+impl PidFile {
+    fn create(path: PathBuf) -> Result<PidFile, Box<dyn std::error::Error>> {
+        let open = || {
+            std::fs::OpenOptions::new()
+                .write(true)
+                .create_new(true)
+                .mode(0o600)
+                .open(&path)
+        };
+
+        let mut file = match open() {
+            Ok(file) => file,
+            Err(error) if error.kind() == std::io::ErrorKind::AlreadyExists => {
+                if File::open(&path).is_ok_and(|existing| is_locked(existing.as_raw_fd())) {
+                    return Err(Box::new(Error::AlreadyPending));
+                }
+                // Left behind by a process that didn't get to clean up.
+                std::fs::remove_file(&path)?;
+                open()?
+            }
+            Err(error) => return Err(Box::new(error)),
+        };
+
+        if unsafe { libc::flock(file.as_raw_fd(), libc::LOCK_EX | libc::LOCK_NB) } != 0 {
+            return Err(Box::new(Error::AlreadyPending));
+        }
+        std::io::Write::write_all(&mut file, std::process::id().to_string().as_bytes())?;
+
+        Ok(PidFile { path, _file: file })
+    }
+}
+
+impl Drop for PidFile {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.path);
+    }
+}
+
+/// Puts the terminal into non-canonical mode so a single key press can be read, restoring it when
+/// dropped.
+struct RawTerminal(libc::termios);
+
+impl RawTerminal {
+    fn new() -> Option<RawTerminal> {
+        unsafe {
+            if libc::isatty(libc::STDIN_FILENO) != 1 {
+                return None;
+            }
+
+            let mut termios: libc::termios = std::mem::zeroed();
+            if libc::tcgetattr(libc::STDIN_FILENO, &mut termios) != 0 {
+                return None;
+            }
+
+            let original = termios;
+            termios.c_lflag &= !(libc::ICANON | libc::ECHO);
+            termios.c_cc[libc::VMIN] = 1;
+            termios.c_cc[libc::VTIME] = 0;
+            libc::tcsetattr(libc::STDIN_FILENO, libc::TCSANOW, &termios);
+
+            Some(RawTerminal(original))
+        }
+    }
+}
+
+impl Drop for RawTerminal {
+    fn drop(&mut self) {
+        unsafe {
+            libc::tcsetattr(libc::STDIN_FILENO, libc::TCSANOW, &self.0);
+        }
+    }
+}
+
+/// A configuration waiting to be confirmed. Create it before applying the configuration, so that
+/// Ctrl-C or SIGTERM from then on are caught and the caller gets to revert. Dropping it restores the
+/// default signal handlers and removes the pid file.
+pub struct Pending {
+    _pid_file: Option<PidFile>,
+}
+
+impl Pending {
+    pub fn new() -> Result<Pending, Box<dyn std::error::Error>> {
+        // Without a runtime directory only a key press can confirm.
+        let pid_file = pid_file().map(PidFile::create).transpose()?;
+
+        CONFIRMED.store(false, Ordering::SeqCst);
+        INTERRUPTED.store(false, Ordering::SeqCst);
+        unsafe {
+            libc::signal(
+                libc::SIGUSR1,
+                confirm_received as extern "C" fn(libc::c_int) as libc::sighandler_t,
+            );
+            for signal in [libc::SIGINT, libc::SIGTERM, libc::SIGHUP] {
+                libc::signal(
+                    signal,
+                    interrupt_received as extern "C" fn(libc::c_int) as libc::sighandler_t,
+                );
+            }
+        }
+
+        Ok(Pending {
+            _pid_file: pid_file,
+        })
+    }
+
+    /// Whether `gnome-randr confirm` can be used, as opposed to only a key press.
+    pub fn can_confirm_remotely(&self) -> bool {
+        self._pid_file.is_some()
+    }
+
+    /// Waits until the user presses a key or runs `gnome-randr confirm`, the timeout passes or the
+    /// process is asked to stop.
+    pub fn wait(&self, timeout: Duration) -> Outcome {
+        let _terminal = RawTerminal::new();
+        // Stop watching stdin once it's closed, e.g. when it isn't attached to anything.
+        let mut stdin_open = true;
+        let deadline = Instant::now() + timeout;
+
+        loop {
+            if INTERRUPTED.load(Ordering::SeqCst) {
+                return Outcome::Interrupted;
+            }
+            if CONFIRMED.load(Ordering::SeqCst) {
+                return Outcome::Confirmed;
+            }
+
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if remaining.is_zero() {
+                return Outcome::TimedOut;
+            }
+            let wait = remaining.min(Duration::from_millis(100));
+
+            if !stdin_open {
+                std::thread::sleep(wait);
+                continue;
+            }
+
+            let mut fds = libc::pollfd {
+                fd: libc::STDIN_FILENO,
+                events: libc::POLLIN,
+                revents: 0,
+            };
+            // A signal interrupts the poll, which is picked up on the next iteration.
+            if unsafe { libc::poll(&mut fds, 1, wait.as_millis() as libc::c_int) } > 0 {
+                let mut key = [0u8; 1];
+                match std::io::stdin().read(&mut key) {
+                    Ok(1) => return Outcome::Confirmed,
+                    _ => stdin_open = false,
+                }
+            }
+        }
+    }
+}
+
+impl Drop for Pending {
+    fn drop(&mut self) {
+        unsafe {
+            for signal in [libc::SIGUSR1, libc::SIGINT, libc::SIGTERM, libc::SIGHUP] {
+                libc::signal(signal, libc::SIG_DFL);
+            }
+        }
+    }
+}
+
+pub fn handle(_: &CommandOptions) -> Result<(), Box<dyn std::error::Error>> {
+    let path = pid_file().ok_or(Error::NothingPending)?;
+    let mut file = File::open(path).map_err(|_| Error::NothingPending)?;
+
+    // Only a process that is still waiting holds the lock, so a pid left behind by one that died
+    // (and possibly reused since) is never signalled.
+    if !is_locked(file.as_raw_fd()) {
+        return Err(Box::new(Error::NothingPending));
+    }
+
+    let mut pid = String::new();
+    file.read_to_string(&mut pid)?;
+    let pid: libc::pid_t = pid.trim().parse().map_err(|_| Error::NothingPending)?;
+
+    if pid <= 0 || unsafe { libc::kill(pid, libc::SIGUSR1) } != 0 {
+        return Err(Box::new(Error::NothingPending));
+    }
+
+    println!("confirmed the new configuration.");
+    Ok(())
+}
@@ -0,0 +1,301 @@
+use std::{
+    path::PathBuf,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+    time::{Duration, Instant},
+};
+
+use dbus::blocking::{Connection, Proxy};
+use gnome_randr::DisplayConfig;
+use serde::Deserialize;
+use structopt::StructOpt;
+
+use super::profile::{config_dir, MonitorId, Profile};
+
+#[derive(StructOpt)]
+pub struct CommandOptions {
+    #[structopt(
+        long,
+        parse(from_os_str),
+        help = "The rules file to read",
+        long_help = "The rules file to read. Defaults to $XDG_CONFIG_HOME/gnome-randr/rules.json. If it doesn't exist, every saved profile is a rule matching the monitors it was saved with."
+    )]
+    pub rules: Option<PathBuf>,
+
+    #[structopt(
+        long,
+        default_value = "1500",
+        value_name = "ms",
+        help = "How long the monitors must stay unchanged before a layout is applied"
+    )]
+    pub debounce: u64,
+
+    #[structopt(
+        short,
+        long,
+        help = "Attempt to replicate the applied configurations the next time the HW layout appears"
+    )]
+    pub persistent: bool,
+
+    #[structopt(
+        long,
+        help = "Log the layouts that would be applied without applying them"
+    )]
+    pub dry_run: bool,
+}
+
+/// A rule applies `profile` when exactly the listed monitors are connected. Without a monitor list,
+/// the monitors the profile was saved with are used.
+#[derive(Deserialize)]
+pub struct Rule {
+    pub profile: String,
+    #[serde(default)]
+    pub monitors: Option<Vec<MonitorId>>,
+}
+
+#[derive(Debug)]
+pub enum Error {
+    InvalidRules(PathBuf, String),
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self {
+            Error::InvalidRules(path, error) => write!(
+                f,
+                "fatal: unable to read rules from {}: {}",
+                path.display(),
+                error
+            ),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+static RELOAD: AtomicBool = AtomicBool::new(false);
+
+extern "C" fn request_reload(_: libc::c_int) {
+    RELOAD.store(true, Ordering::SeqCst);
+}
+
+fn load_rules(path: &std::path::Path) -> Result<Vec<Rule>, Box<dyn std::error::Error>> {
+    if !path.exists() {
+        println!(
+            "no rules file at {}, matching saved profiles by their monitors",
+            path.display()
+        );
+        return Ok(Profile::list()?
+            .into_iter()
+            .map(|profile| Rule {
+                profile,
+                monitors: None,
+            })
+            .collect());
+    }
+
+    let contents = std::fs::read_to_string(path)
+        .map_err(|error| Error::InvalidRules(path.to_path_buf(), error.to_string()))?;
+    let rules: Vec<Rule> = serde_json::from_str(&contents)
+        .map_err(|error| Error::InvalidRules(path.to_path_buf(), error.to_string()))?;
+    println!("loaded {} rules from {}", rules.len(), path.display());
+    Ok(rules)
+}
+
+/// Whether both lists hold the same monitors, counting identical monitors separately.
+fn same_monitors(a: &[&MonitorId], b: &[MonitorId]) -> bool {
+    fn sorted<'a>(
+        monitors: impl Iterator<Item = &'a MonitorId>,
+    ) -> Vec<(&'a str, &'a str, &'a str)> {
+        let mut monitors: Vec<_> = monitors
+            .map(|monitor| (&*monitor.vendor, &*monitor.product, &*monitor.serial))
+            .collect();
+        monitors.sort_unstable();
+        monitors
+    }
+
+    sorted(a.iter().copied()) == sorted(b.iter())
+}
+
+/// The first rule matching exactly the connected monitors, along with its profile.
+fn find_rule<'a>(rules: &'a [Rule], connected: &[MonitorId]) -> Option<(&'a Rule, Profile)> {
+    rules.iter().find_map(|rule| {
+        let profile = match Profile::load(&rule.profile) {
+            Ok(profile) => profile,
+            Err(error) => {
+                eprintln!("{}", error);
+                return None;
+            }
+        };
+
+        let matches = match &rule.monitors {
+            Some(monitors) => same_monitors(&monitors.iter().collect::<Vec<_>>(), connected),
+            None => same_monitors(&profile.monitor_ids(), connected),
+        };
+
+        if matches {
+            Some((rule, profile))
+        } else {
+            None
+        }
+    })
+}
+
+/// Applies the rule matching the connected monitors, unless they are the same monitors as last
+/// time. This keeps the daemon from undoing changes the user makes by hand.
+fn update(
+    opts: &CommandOptions,
+    rules: &[Rule],
+    last_connected: &mut Option<Vec<MonitorId>>,
+    proxy: &Proxy<&Connection>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let config = DisplayConfig::get_current_state(proxy)?;
+    let connected: Vec<MonitorId> = config.monitors.iter().map(MonitorId::from).collect();
+
+    if last_connected.as_ref() == Some(&connected) {
+        return Ok(());
+    }
+    *last_connected = Some(connected.clone());
+
+    println!(
+        "connected monitors: {}",
+        connected
+            .iter()
+            .map(|monitor| monitor.to_string())
+            .collect::<Vec<_>>()
+            .join(", ")
+    );
+
+    let (rule, profile) = match find_rule(rules, &connected) {
+        Some(found) => found,
+        None => {
+            println!("no rule matches, leaving the configuration alone");
+            return Ok(());
+        }
+    };
+
+    if profile.matches(&Profile::from(&config)) {
+        println!("profile '{}' is already applied", rule.profile);
+        return Ok(());
+    }
+
+    let errors = profile.validate(&config)?;
+    if !errors.is_empty() {
+        eprintln!(
+            "not applying profile '{}', it is invalid for the connected monitors:",
+            rule.profile
+        );
+        for error in errors.iter() {
+            eprintln!("\t{}", error);
+        }
+        return Ok(());
+    }
+
+    if opts.dry_run {
+        println!("dry run: would apply profile '{}'", rule.profile);
+        return Ok(());
+    }
+
+    println!("applying profile '{}'", rule.profile);
+    profile.apply(&config, proxy, opts.persistent, false)
+}
+
+pub fn handle(
+    opts: &CommandOptions,
+    proxy: &Proxy<&Connection>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let path = match &opts.rules {
+        Some(path) => path.clone(),
+        None => config_dir()?.join("rules.json"),
+    };
+    let mut rules = load_rules(&path)?;
+
+    unsafe {
+        libc::signal(
+            libc::SIGHUP,
+            request_reload as extern "C" fn(libc::c_int) as libc::sighandler_t,
+        );
+    }
+
+    // Docks tend to connect and disconnect monitors several times in a row, so only act once the
+    // monitors have stopped changing for a while.
+    let debounce = Duration::from_millis(opts.debounce);
+    let changed_at = Arc::new(Mutex::new(None));
+    DisplayConfig::on_monitors_changed(proxy, {
+        let changed_at = changed_at.clone();
+        move || {
+            *changed_at.lock().unwrap() = Some(Instant::now());
+            true
+        }
+    })?;
+
+    let mut last_connected = None;
+    if let Err(error) = update(opts, &rules, &mut last_connected, proxy) {
+        eprintln!("{}", error);
+    }
+
+    loop {
+        proxy.connection.process(Duration::from_millis(250))?;
+
+        if RELOAD.swap(false, Ordering::SeqCst) {
+            println!("reloading rules");
+            match load_rules(&path) {
+                Ok(reloaded) => rules = reloaded,
+                Err(error) => eprintln!("{}", error),
+            }
+            // Re-evaluate the connected monitors against the new rules.
+            last_connected = None;
+            if let Err(error) = update(opts, &rules, &mut last_connected, proxy) {
+                eprintln!("{}", error);
+            }
+        }
+
+        let settled = {
+            let mut changed_at = changed_at.lock().unwrap();
+            match *changed_at {
+                Some(at) if at.elapsed() >= debounce => {
+                    *changed_at = None;
+                    true
+                }
+                _ => false,
+            }
+        };
+
+        if settled {
+            if let Err(error) = update(opts, &rules, &mut last_connected, proxy) {
+                eprintln!("{}", error);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn monitor(product: &str) -> MonitorId {
+        MonitorId {
+            vendor: "DEL".to_string(),
+            product: product.to_string(),
+            serial: String::new(),
+        }
+    }
+
+    #[test]
+    fn counts_identical_monitors_separately() {
+        let two = vec![monitor("DELL U2720Q"), monitor("DELL U2720Q")];
+        let one = vec![monitor("DELL U2720Q")];
+        let mixed = vec![monitor("DELL U2720Q"), monitor("DELL P2419H")];
+
+        assert!(same_monitors(&two.iter().collect::<Vec<_>>(), &two));
+        assert!(!same_monitors(&one.iter().collect::<Vec<_>>(), &two));
+        assert!(!same_monitors(&two.iter().collect::<Vec<_>>(), &one));
+        assert!(!same_monitors(&mixed.iter().collect::<Vec<_>>(), &two));
+        assert!(same_monitors(
+            &mixed.iter().rev().collect::<Vec<_>>(),
+            &mixed
+        ));
+    }
+}
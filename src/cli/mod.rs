@@ -5,10 +5,16 @@ use structopt::StructOpt;
 
 use gnome_randr::DisplayConfig;
 
+pub mod completions;
+pub mod confirm;
+pub mod daemon;
 pub mod modify;
+pub mod profile;
 pub mod query;
+pub mod watch;
 
 #[derive(StructOpt)]
+#[allow(clippy::large_enum_variant)]
 enum Command {
     #[structopt(
         about = "Query returns information about the current state of the monitors. This is the default subcommand."
@@ -16,6 +22,22 @@ enum Command {
     Query(query::CommandOptions),
     #[structopt(about = "Modify allows you to alter the current display configuration.")]
     Modify(modify::CommandOptions),
+    #[structopt(
+        about = "Profile saves, lists and applies named display configurations, matching monitors by vendor, product and serial."
+    )]
+    Profile(profile::CommandOptions),
+    #[structopt(
+        about = "Daemon watches for monitors being connected or disconnected and applies the profile matching them."
+    )]
+    Daemon(daemon::CommandOptions),
+    #[structopt(about = "Confirm keeps the configuration applied by `modify --confirm`.")]
+    Confirm(confirm::CommandOptions),
+    #[structopt(
+        about = "Watch prints monitors being connected, disconnected or reconfigured as it happens."
+    )]
+    Watch(watch::CommandOptions),
+    #[structopt(about = "Completions prints a completion script for the given shell.")]
+    Completions(completions::CommandOptions),
 }
 
 #[derive(StructOpt)]
@@ -29,9 +51,23 @@ struct CLI {
 }
 
 pub fn run() -> Result<(), Box<dyn std::error::Error>> {
+    // The completion scripts call this hidden entry point with the words being completed, which
+    // may be anything, so it bypasses the argument parser and stays out of the help and completions.
+    if std::env::args().nth(1).as_deref() == Some("__complete") {
+        return completions::complete(&std::env::args().skip(2).collect::<Vec<_>>());
+    }
+
     // Parse the CLI args. We do this first to short-circuit the dbus calls if there's an invalid arg.
     let args = CLI::from_args();
 
+    // These don't need the display server, or only connect to it when they have to.
+    match &args.cmd {
+        // Confirming only signals the waiting `modify`.
+        Some(Command::Confirm(opts)) => return confirm::handle(opts),
+        Some(Command::Completions(opts)) => return completions::handle(opts, CLI::clap()),
+        _ => {}
+    }
+
     // Open up a connection to the session bus.
     let conn = Connection::new_session()?;
 
@@ -46,14 +82,19 @@ pub fn run() -> Result<(), Box<dyn std::error::Error>> {
     let config = DisplayConfig::get_current_state(&proxy)?;
 
     // See what we're executing
-    let cmd = args.cmd.unwrap_or(Command::Query(query::CommandOptions {
-        connector: None,
-        summary: false,
-    }));
+    let cmd = args
+        .cmd
+        .unwrap_or_else(|| Command::Query(query::CommandOptions::default()));
 
     match cmd {
         Command::Query(opts) => print!("{}", query::handle(&opts, &config)?),
         Command::Modify(opts) => modify::handle(&opts, &config, &proxy)?,
+        Command::Profile(opts) => profile::handle(&opts, &config, &proxy)?,
+        Command::Daemon(opts) => daemon::handle(&opts, &proxy)?,
+        Command::Watch(opts) => watch::handle(&opts, &proxy)?,
+        Command::Confirm(_) | Command::Completions(_) => {
+            unreachable!("handled before connecting to the display server")
+        }
     }
 
     Ok(())
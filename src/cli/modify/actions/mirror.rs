@@ -0,0 +1,81 @@
+use gnome_randr::{
+    display_config::{physical_monitor::PhysicalMonitor, ApplyConfig, ApplyMonitor},
+    DisplayConfig,
+};
+
+use super::{super::Error, Action};
+
+pub struct MirrorAction<'a> {
+    pub target: &'a str,
+    pub width: i32,
+    pub height: i32,
+    pub monitors: Vec<ApplyMonitor<'a>>,
+}
+
+impl<'a> MirrorAction<'a> {
+    /// Picks the largest resolution shared by the monitor being added and every monitor already in
+    /// the target logical monitor. Monitors keep their current mode where possible so they don't
+    /// needlessly switch modes.
+    pub fn new(
+        target: &'a str,
+        group: &ApplyConfig<'a>,
+        physical_monitor: &'a PhysicalMonitor,
+        config: &'a DisplayConfig,
+    ) -> Result<MirrorAction<'a>, Error> {
+        let members: Vec<&PhysicalMonitor> = group
+            .monitors
+            .iter()
+            .filter_map(|monitor| config.search_physical(monitor.connector))
+            .filter(|monitor| monitor.connector != physical_monitor.connector)
+            .chain(std::iter::once(physical_monitor))
+            .collect();
+
+        let (width, height) = physical_monitor
+            .modes
+            .iter()
+            .map(|mode| (mode.width, mode.height))
+            .filter(|resolution| {
+                members
+                    .iter()
+                    .all(|member| member.find_mode(resolution.0, resolution.1, None).is_some())
+            })
+            .max_by_key(|(width, height)| (width * height, *width))
+            .ok_or(Error::NoSharedMode)?;
+
+        let monitors = members
+            .iter()
+            .map(|member| {
+                member
+                    .find_mode(width, height, None)
+                    .map(|mode| ApplyMonitor {
+                        connector: &member.connector,
+                        mode_id: &mode.id,
+                    })
+            })
+            .collect::<Option<Vec<_>>>()
+            .ok_or(Error::NoSharedMode)?;
+
+        Ok(MirrorAction {
+            target,
+            width,
+            height,
+            monitors,
+        })
+    }
+}
+
+impl<'a> Action<'a> for MirrorAction<'a> {
+    fn apply(&self, config: &mut ApplyConfig<'a>, _: &PhysicalMonitor) {
+        config.monitors = self.monitors.clone();
+    }
+}
+
+impl std::fmt::Display for MirrorAction<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "mirroring {} at {}x{}",
+            self.target, self.width, self.height
+        )
+    }
+}
@@ -1,11 +1,15 @@
+mod mirror;
 mod mode;
+mod position;
 mod primary;
 mod rotation;
 mod scale;
 
 use gnome_randr::display_config::{physical_monitor::PhysicalMonitor, ApplyConfig};
 
+pub use mirror::MirrorAction;
 pub use mode::ModeAction;
+pub use position::{Placement, PositionAction, Side};
 pub use primary::PrimaryAction;
 pub use rotation::RotationAction;
 pub use scale::ScaleAction;
@@ -0,0 +1,202 @@
+use gnome_randr::display_config::{physical_monitor::PhysicalMonitor, ApplyConfig, LayoutMode};
+
+use super::{
+    super::{Alignment, Position},
+    Action,
+};
+
+#[derive(Clone, Copy)]
+pub enum Side {
+    LeftOf,
+    RightOf,
+    Above,
+    Below,
+}
+
+impl std::fmt::Display for Side {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Side::LeftOf => "left of",
+                Side::RightOf => "right of",
+                Side::Above => "above",
+                Side::Below => "below",
+            }
+        )
+    }
+}
+
+pub enum Placement<'a> {
+    Absolute(Position),
+    Relative {
+        side: Side,
+        target: ApplyConfig<'a>,
+        target_monitor: &'a PhysicalMonitor,
+        align: Alignment,
+    },
+}
+
+pub struct PositionAction<'a> {
+    pub placement: Placement<'a>,
+    pub layout_mode: LayoutMode,
+}
+
+/// Where a span of length `own` starts when aligned against a span of length `other` starting at
+/// `start`.
+fn align(alignment: Alignment, start: i32, other: i32, own: i32) -> i32 {
+    match alignment {
+        Alignment::Start => start,
+        Alignment::Center => start + (other - own) / 2,
+        Alignment::End => start + other - own,
+    }
+}
+
+impl Action<'_> for PositionAction<'_> {
+    fn apply(&self, config: &mut ApplyConfig, physical_monitor: &PhysicalMonitor) {
+        let (x, y) = match &self.placement {
+            Placement::Absolute(position) => (position.x, position.y),
+            Placement::Relative {
+                side,
+                target,
+                target_monitor,
+                align: alignment,
+            } => {
+                let (width, height) = config
+                    .logical_size(std::slice::from_ref(physical_monitor), self.layout_mode)
+                    .unwrap_or((0, 0));
+                let (target_width, target_height) = target
+                    .logical_size(std::slice::from_ref(*target_monitor), self.layout_mode)
+                    .unwrap_or((0, 0));
+
+                match side {
+                    Side::LeftOf => (
+                        target.x_pos - width,
+                        align(*alignment, target.y_pos, target_height, height),
+                    ),
+                    Side::RightOf => (
+                        target.x_pos + target_width,
+                        align(*alignment, target.y_pos, target_height, height),
+                    ),
+                    Side::Above => (
+                        align(*alignment, target.x_pos, target_width, width),
+                        target.y_pos - height,
+                    ),
+                    Side::Below => (
+                        align(*alignment, target.x_pos, target_width, width),
+                        target.y_pos + target_height,
+                    ),
+                }
+            }
+        };
+
+        config.x_pos = x;
+        config.y_pos = y;
+    }
+}
+
+impl std::fmt::Display for PositionAction<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.placement {
+            Placement::Absolute(position) => write!(f, "setting position to {}", position),
+            Placement::Relative {
+                side,
+                target_monitor,
+                align,
+                ..
+            } => write!(
+                f,
+                "placing monitor {} {} (aligned to {})",
+                side, target_monitor.connector, align
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gnome_randr::{display_config::testing, DisplayConfig};
+
+    /// Where the laptop (1440x900 at scale 2) ends up when placed against the monitor (2560x1440
+    /// at 1440x0), after `change` is made to the monitor.
+    fn place(
+        config: &DisplayConfig,
+        side: Side,
+        align: Alignment,
+        change: impl Fn(&mut ApplyConfig),
+    ) -> (i32, i32) {
+        let mut laptop = ApplyConfig::from(&config.logical_monitors[0], &config.monitors);
+        let mut target = ApplyConfig::from(&config.logical_monitors[1], &config.monitors);
+        change(&mut target);
+
+        PositionAction {
+            placement: Placement::Relative {
+                side,
+                target,
+                target_monitor: &config.monitors[1],
+                align,
+            },
+            layout_mode: LayoutMode::Logical,
+        }
+        .apply(&mut laptop, &config.monitors[0]);
+        (laptop.x_pos, laptop.y_pos)
+    }
+
+    #[test]
+    fn aligns_along_every_side() {
+        let config = testing::laptop_and_monitor();
+        let cases = [
+            (Side::LeftOf, [(0, 0), (0, 270), (0, 540)]),
+            (Side::RightOf, [(4000, 0), (4000, 270), (4000, 540)]),
+            (Side::Above, [(1440, -900), (2000, -900), (2560, -900)]),
+            (Side::Below, [(1440, 1440), (2000, 1440), (2560, 1440)]),
+        ];
+
+        for (side, expected) in cases.iter() {
+            let aligns = [Alignment::Start, Alignment::Center, Alignment::End];
+            for (align, position) in aligns.iter().zip(expected.iter()) {
+                assert_eq!(
+                    place(&config, *side, *align, |_| {}),
+                    *position,
+                    "{} aligned to {}",
+                    side,
+                    align
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn uses_the_targets_rotated_and_scaled_size() {
+        let config = testing::laptop_and_monitor();
+
+        // Rotated, the monitor is 1440x2560.
+        let rotate = |target: &mut ApplyConfig| target.transform = 1;
+        assert_eq!(
+            place(&config, Side::RightOf, Alignment::End, rotate),
+            (2880, 1660)
+        );
+
+        // At scale 2, the monitor is 1280x720, narrower than the laptop.
+        let scale = |target: &mut ApplyConfig| target.scale = 2.0;
+        assert_eq!(
+            place(&config, Side::Below, Alignment::Center, scale),
+            (1360, 720)
+        );
+    }
+
+    #[test]
+    fn sets_absolute_positions() {
+        let config = testing::laptop_and_monitor();
+        let mut laptop = ApplyConfig::from(&config.logical_monitors[0], &config.monitors);
+
+        PositionAction {
+            placement: Placement::Absolute(Position { x: 4000, y: 200 }),
+            layout_mode: LayoutMode::Logical,
+        }
+        .apply(&mut laptop, &config.monitors[0]);
+        assert_eq!((laptop.x_pos, laptop.y_pos), (4000, 200));
+    }
+}
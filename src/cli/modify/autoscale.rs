@@ -0,0 +1,258 @@
+use gnome_randr::{
+    display_config::{
+        physical_monitor::{Mode, PhysicalMonitor},
+        ApplyConfig,
+    },
+    DisplayConfig,
+};
+
+use super::actions::{Action, ScaleAction};
+
+// The effective DPIs mutter aims for when it picks a default scale. Laptop panels are viewed from
+// closer up than external monitors, so they get a higher target.
+const BUILTIN_TARGET_DPI: f64 = 135.0;
+const EXTERNAL_TARGET_DPI: f64 = 110.0;
+
+/// Parses --target-dpi, which has to be a positive number for the costs to mean anything.
+pub fn parse_target_dpi(s: &str) -> Result<f64, String> {
+    match s.parse::<f64>() {
+        Ok(dpi) if dpi.is_finite() && dpi > 0.0 => Ok(dpi),
+        _ => Err(format!("'{}' is not a positive number", s)),
+    }
+}
+
+struct Member<'a> {
+    physical_monitor: &'a PhysicalMonitor,
+    mode: &'a Mode,
+    dpi: Option<f64>,
+    target_dpi: f64,
+}
+
+impl Member<'_> {
+    /// How far the monitor's effective DPI would be from its target, relative to the target.
+    fn cost(&self, scale: f64) -> f64 {
+        self.dpi.map_or(0.0, |dpi| {
+            ((dpi / scale) - self.target_dpi).abs() / self.target_dpi
+        })
+    }
+
+    /// Describes the monitor's density, e.g. "14.0in at 2880x1800 is 242 DPI (target 135)".
+    fn describe(&self, target_dpi: Option<f64>) -> String {
+        let target = match target_dpi {
+            Some(_) => format!("target {:.0}", self.target_dpi),
+            None if self.physical_monitor.known_properties.is_builtin => format!(
+                "target {:.0}, the default for built-in monitors",
+                self.target_dpi
+            ),
+            None => format!(
+                "target {:.0}, the default for external monitors",
+                self.target_dpi
+            ),
+        };
+
+        match (
+            self.physical_monitor.known_properties.diagonal_inches(),
+            self.dpi,
+        ) {
+            (Some(diagonal), Some(dpi)) => format!(
+                "{:.1}in at {}x{} is {:.0} DPI ({})",
+                diagonal, self.mode.width, self.mode.height, dpi, target
+            ),
+            _ => format!(
+                "physical size is {}, so it is left out of the choice",
+                self.physical_monitor.known_properties.physical_size()
+            ),
+        }
+    }
+}
+
+fn members<'a>(
+    apply_config: &ApplyConfig,
+    config: &'a DisplayConfig,
+    target_dpi: Option<f64>,
+) -> Vec<Member<'a>> {
+    apply_config
+        .monitors
+        .iter()
+        .filter_map(|monitor| {
+            let physical_monitor = config.search_physical(monitor.connector)?;
+            let mode = physical_monitor
+                .modes
+                .iter()
+                .find(|mode| mode.id == monitor.mode_id)?;
+
+            Some(Member {
+                physical_monitor,
+                mode,
+                dpi: physical_monitor.dpi(mode),
+                target_dpi: target_dpi.unwrap_or(if physical_monitor.known_properties.is_builtin {
+                    BUILTIN_TARGET_DPI
+                } else {
+                    EXTERNAL_TARGET_DPI
+                }),
+            })
+        })
+        .collect()
+}
+
+/// The scales every member supports, as advertised for the first member's mode.
+fn common_scales(members: &[&Member]) -> Vec<f64> {
+    match members.first() {
+        Some(first) => first
+            .mode
+            .supported_scales
+            .iter()
+            .copied()
+            .filter(|scale| {
+                members
+                    .iter()
+                    .all(|member| member.mode.find_scale(*scale).is_some())
+            })
+            .collect(),
+        None => Vec::new(),
+    }
+}
+
+/// The scale from `scales` that brings the members closest to their target DPI.
+fn best_scale(members: &[&Member], scales: &[f64]) -> Option<f64> {
+    let cost = |scale: f64| members.iter().map(|member| member.cost(scale)).sum::<f64>();
+
+    scales
+        .iter()
+        .copied()
+        .min_by(|a, b| cost(*a).total_cmp(&cost(*b)))
+}
+
+fn describe_choice(members: &[&Member], scale: f64, scales: &[f64]) -> String {
+    let effective: Vec<(&str, f64)> = members
+        .iter()
+        .filter_map(|member| {
+            Some((
+                member.physical_monitor.connector.as_str(),
+                member.dpi? / scale,
+            ))
+        })
+        .collect();
+
+    let effective = match effective.as_slice() {
+        [(_, dpi)] => format!("an effective DPI of {:.0}", dpi),
+        _ => format!(
+            "effective DPIs of {}",
+            effective
+                .iter()
+                .map(|(connector, dpi)| format!("{:.0} on {}", dpi, connector))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+    };
+
+    format!(
+        "scale {} gives {}, the closest of {}",
+        scale,
+        effective,
+        scales
+            .iter()
+            .map(|scale| scale.to_string())
+            .collect::<Vec<_>>()
+            .join(", ")
+    )
+}
+
+/// Picks a scale for every logical monitor (other than those containing a connector in `skip`)
+/// that brings it closest to the target effective DPI, explaining each choice. With
+/// `global_scale` every logical monitor gets the scale that suits all of them best. Returns whether
+/// any scale changed.
+pub fn auto_scale<'a>(
+    all_configs: &mut [ApplyConfig<'a>],
+    config: &'a DisplayConfig,
+    target_dpi: Option<f64>,
+    skip: &[&str],
+    global_scale: bool,
+) -> bool {
+    let groups: Vec<(usize, Vec<Member>)> = all_configs
+        .iter()
+        .enumerate()
+        .filter(|(_, apply_config)| {
+            !apply_config
+                .monitors
+                .iter()
+                .any(|monitor| skip.contains(&monitor.connector))
+        })
+        .map(|(i, apply_config)| (i, members(apply_config, config, target_dpi)))
+        .collect();
+
+    for member in groups.iter().flat_map(|(_, members)| members.iter()) {
+        println!(
+            "{}: {}",
+            member.physical_monitor.connector,
+            member.describe(target_dpi)
+        );
+    }
+
+    // Every group gets its own scale, unless the display server requires a single one.
+    let choices: Vec<(Vec<usize>, Vec<&Member>)> = if global_scale {
+        vec![(
+            groups.iter().map(|(i, _)| *i).collect(),
+            groups
+                .iter()
+                .flat_map(|(_, members)| members.iter())
+                .collect(),
+        )]
+    } else {
+        groups
+            .iter()
+            .map(|(i, members)| (vec![*i], members.iter().collect()))
+            .collect()
+    };
+
+    let mut changed = false;
+    for (indices, members) in choices.iter() {
+        let name = members
+            .iter()
+            .map(|member| member.physical_monitor.connector.as_str())
+            .collect::<Vec<_>>()
+            .join(" + ");
+
+        if members.iter().all(|member| member.dpi.is_none()) {
+            println!("{}: physical size unknown, keeping the current scale", name);
+            continue;
+        }
+
+        let scales = common_scales(members);
+        let scale = match best_scale(members, &scales) {
+            Some(scale) => scale,
+            None => {
+                println!(
+                    "{}: no scale is supported by every monitor, keeping the current scale",
+                    name
+                );
+                continue;
+            }
+        };
+        println!("{}: {}", name, describe_choice(members, scale, &scales));
+
+        for &i in indices.iter() {
+            let apply_config = &mut all_configs[i];
+            if apply_config.scale == scale {
+                continue;
+            }
+
+            let action = ScaleAction { scale };
+            let physical_monitor = members[0].physical_monitor;
+            action.apply(apply_config, physical_monitor);
+            println!(
+                "{}: {}",
+                apply_config
+                    .monitors
+                    .iter()
+                    .map(|monitor| monitor.connector)
+                    .collect::<Vec<_>>()
+                    .join(" + "),
+                action
+            );
+            changed = true;
+        }
+    }
+
+    changed
+}
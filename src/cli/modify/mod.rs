@@ -1,9 +1,25 @@
 mod actions;
+mod autoscale;
 
-use gnome_randr::{display_config::ApplyConfig, DisplayConfig};
+use gnome_randr::{
+    display_config::{
+        diff::ConfigDiff,
+        logical_monitor::Transform,
+        physical_monitor::{Mode, ModeSelector, PhysicalMonitor},
+        selector,
+        validation::ValidationError,
+        ApplyConfig, ApplyMonitor, LayoutMode,
+    },
+    DisplayConfig,
+};
 use structopt::StructOpt;
 
-use self::actions::{Action, ModeAction, PrimaryAction, RotationAction, ScaleAction};
+use super::confirm::Outcome;
+
+use self::actions::{
+    Action, MirrorAction, ModeAction, Placement, PositionAction, PrimaryAction, RotationAction,
+    ScaleAction, Side,
+};
 
 #[derive(Clone, Copy)]
 pub enum Rotation {
@@ -42,6 +58,91 @@ impl std::fmt::Display for Rotation {
     }
 }
 
+#[derive(Clone, Copy)]
+pub enum Alignment {
+    Start,
+    Center,
+    End,
+}
+
+impl std::str::FromStr for Alignment {
+    type Err = std::fmt::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "start" => Ok(Alignment::Start),
+            "center" | "centre" => Ok(Alignment::Center),
+            "end" => Ok(Alignment::End),
+            _ => Err(std::fmt::Error),
+        }
+    }
+}
+
+impl std::fmt::Display for Alignment {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Alignment::Start => "start",
+                Alignment::Center => "center",
+                Alignment::End => "end",
+            }
+        )
+    }
+}
+
+#[derive(Clone, Copy)]
+pub struct Position {
+    pub x: i32,
+    pub y: i32,
+}
+
+impl std::str::FromStr for Position {
+    type Err = std::fmt::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (x, y) = s.split_once('x').ok_or(std::fmt::Error)?;
+
+        Ok(Position {
+            x: x.trim().parse().map_err(|_| std::fmt::Error)?,
+            y: y.trim().parse().map_err(|_| std::fmt::Error)?,
+        })
+    }
+}
+
+impl std::fmt::Display for Position {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}x{}", self.x, self.y)
+    }
+}
+
+#[derive(Clone, Copy)]
+pub enum Scale {
+    Exact(f64),
+    Nearest(f64),
+}
+
+impl std::str::FromStr for Scale {
+    type Err = std::num::ParseFloatError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.strip_prefix("nearest:") {
+            Some(scale) => Ok(Scale::Nearest(scale.trim().parse()?)),
+            None => Ok(Scale::Exact(s.trim().parse()?)),
+        }
+    }
+}
+
+impl std::fmt::Display for Scale {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Scale::Exact(scale) => write!(f, "{}", scale),
+            Scale::Nearest(scale) => write!(f, "nearest:{}", scale),
+        }
+    }
+}
+
 #[derive(StructOpt)]
 pub struct ActionOptions {
     #[structopt(
@@ -55,25 +156,137 @@ pub struct ActionOptions {
     #[structopt(
         short,
         long,
-        help = "A valid mode for the given display.",
-        long_help = "A valid mode for the given display. To find valid modes use the \"query\" subcommand"
+        help = "A valid mode for the given display, e.g. '2560x1440' or 'preferred'",
+        long_help = "A valid mode for the given display. This can be a resolution such as '2560x1440' (optionally with a refresh rate, as in '2560x1440@144'), a mode ID as listed by the \"query\" subcommand, or one of the keywords 'preferred', 'native' (the largest resolution) or 'highest-refresh' (the highest refresh rate at the current resolution)."
+    )]
+    pub mode: Option<ModeSelector>,
+
+    #[structopt(
+        long,
+        help = "The refresh rate to use, e.g. '144'",
+        long_help = "The refresh rate to use, e.g. '144'. The mode with the closest refresh rate at the chosen resolution (or the current one if --mode isn't given) is picked."
     )]
-    pub mode: Option<String>,
+    pub rate: Option<f64>,
 
     #[structopt(long, help = "Set the given monitor as the primary logical monitor")]
     pub primary: bool,
 
-    #[structopt(long, help = "Set the scale")]
-    pub scale: Option<f64>,
+    #[structopt(
+        long,
+        help = "Set the scale, e.g. '2' or 'nearest:1.3'",
+        long_help = "Set the scale, e.g. '2'. The scale must be supported by the monitor's mode (see the \"query\" subcommand); use 'nearest:1.3' to pick the supported scale closest to 1.3. If the display server requires all monitors to share a scale, it is applied to every monitor."
+    )]
+    pub scale: Option<Scale>,
+
+    #[structopt(
+        long,
+        value_name = "XxY",
+        conflicts_with_all = &["left-of", "right-of", "above", "below"],
+        help = "Set the position of the monitor, e.g. '1920x0'",
+        long_help = "Set the position of the top-left corner of the monitor in the global screen space, e.g. '1920x0'. The layout is shifted afterwards so that it starts at 0x0, which makes the position relative to the other monitors: with eDP-1 at 0x0 and DP-1 at 1440x0, `modify eDP-1 --pos 4000x0` moves eDP-1 to the right of DP-1."
+    )]
+    pub pos: Option<Position>,
+
+    #[structopt(
+        long,
+        value_name = "connector",
+        conflicts_with_all = &["right-of", "above", "below"],
+        help = "Place the monitor to the left of the given connector"
+    )]
+    pub left_of: Option<String>,
+
+    #[structopt(
+        long,
+        value_name = "connector",
+        conflicts_with_all = &["above", "below"],
+        help = "Place the monitor to the right of the given connector"
+    )]
+    pub right_of: Option<String>,
+
+    #[structopt(
+        long,
+        value_name = "connector",
+        conflicts_with = "below",
+        help = "Place the monitor above the given connector"
+    )]
+    pub above: Option<String>,
+
+    #[structopt(
+        long,
+        value_name = "connector",
+        help = "Place the monitor below the given connector"
+    )]
+    pub below: Option<String>,
+
+    #[structopt(
+        long,
+        default_value = "start",
+        help = "One of 'start', 'center' or 'end'",
+        long_help = "One of 'start', 'center' or 'end'. When placing a monitor relative to another, this aligns it along the other axis: with --left-of/--right-of 'start' lines up the top edges, and with --above/--below it lines up the left edges."
+    )]
+    pub align: Alignment,
+
+    #[structopt(
+        long,
+        conflicts_with_all = &["auto", "rotation", "mode", "primary", "scale", "pos", "left-of", "right-of", "above", "below"],
+        help = "Disable the given monitor",
+        long_help = "Disable the given monitor. If it was the primary monitor, another enabled monitor becomes primary."
+    )]
+    pub off: bool,
+
+    #[structopt(
+        long,
+        conflicts_with = "mode",
+        help = "Enable the given monitor with its preferred mode",
+        long_help = "Enable the given monitor with its preferred mode. A disabled monitor is placed to the right of the existing layout unless a position is given; an enabled monitor is switched to its preferred mode."
+    )]
+    pub auto: bool,
+
+    #[structopt(
+        long,
+        value_name = "connector",
+        conflicts_with_all = &["off", "mode", "rate", "auto", "pos", "left-of", "right-of", "above", "below"],
+        help = "Mirror the given connector",
+        long_help = "Mirror the given connector by joining its logical monitor. Both monitors are switched to the largest resolution they have in common."
+    )]
+    pub same_as: Option<String>,
+}
+
+impl ActionOptions {
+    /// Whether no action was given. --align is left out, since it only refines the others.
+    fn is_empty(&self) -> bool {
+        self.rotation.is_none()
+            && self.mode.is_none()
+            && self.rate.is_none()
+            && !self.primary
+            && self.scale.is_none()
+            && self.pos.is_none()
+            && self.relative_to().is_none()
+            && !self.off
+            && !self.auto
+            && self.same_as.is_none()
+    }
+
+    fn relative_to(&self) -> Option<(Side, &str)> {
+        [
+            (Side::LeftOf, &self.left_of),
+            (Side::RightOf, &self.right_of),
+            (Side::Above, &self.above),
+            (Side::Below, &self.below),
+        ]
+        .iter()
+        .find_map(|(side, connector)| connector.as_deref().map(|connector| (*side, connector)))
+    }
 }
 
 #[derive(StructOpt)]
 pub struct CommandOptions {
     #[structopt(
-        help = "the connector used for the physical monitor.",
-        long_help = "the connector used for the physical monitor you want to modify, e.g. \"HDMI-1\". You can find these with \"query\" (no arguments) if you're unsure."
+        help = "the connector used for the physical monitor, or a selector.",
+        long_help = "the connector used for the physical monitor you want to modify, e.g. \"HDMI-1\". You can find these with \"query\" (no arguments) if you're unsure.\n\nSelectors pick the monitor by something other than its connector, which changes between dock ports: 'vendor:DEL', 'serial:ABC123', 'name:\"Dell U2720Q\"' (the display or product name), 'builtin', 'external', 'primary' or 'logical:N' (the Nth logical monitor listed by \"query\", counting from 0). Connectors and selector values may be globs such as 'DP-*'. The selector must match exactly one monitor, and can also be used wherever another connector is expected, e.g. --left-of builtin.",
+        required_unless_one = &["layout-mode", "outputs", "auto-scale"]
     )]
-    pub connector: String,
+    pub connector: Option<String>,
 
     #[structopt(flatten)]
     pub actions: ActionOptions,
@@ -85,24 +298,186 @@ pub struct CommandOptions {
     )]
     persistent: bool,
 
-    #[structopt(long, help = "List changes without actually applying them")]
+    #[structopt(
+        long,
+        help = "List changes without actually applying them",
+        long_help = "List changes without actually applying them. The new configuration is still sent to the display server to check whether it would be accepted."
+    )]
     dry_run: bool,
+
+    #[structopt(
+        long,
+        help = "Apply the changes even if the new configuration looks invalid",
+        long_help = "Apply the changes even if the new configuration looks invalid, e.g. because monitors overlap or don't touch each other. The problems are still listed, and the display server may reject the configuration anyway."
+    )]
+    force: bool,
+
+    #[structopt(
+        long,
+        value_name = "secs",
+        help = "Revert the changes unless they are confirmed within this many seconds",
+        long_help = "Revert the changes unless they are confirmed within this many seconds, by pressing a key or running `gnome-randr confirm`. The changes are applied temporarily until then, and only persisted (with --persistent) once confirmed."
+    )]
+    confirm: Option<u64>,
+
+    #[structopt(
+        long,
+        help = "Pick each monitor's scale so text appears at a similar physical size",
+        long_help = "Pick each monitor's scale from the ones its mode supports, so that its effective DPI (its DPI divided by its scale) is as close as possible to --target-dpi. Monitors given an explicit --scale are left alone, as are monitors that don't report a real physical size. If the display server requires a single scale for every monitor, the one that suits them all best is used. The monitors are moved to stay next to each other."
+    )]
+    auto_scale: bool,
+
+    #[structopt(
+        long,
+        value_name = "dpi",
+        requires = "auto-scale",
+        parse(try_from_str = autoscale::parse_target_dpi),
+        help = "The effective DPI --auto-scale aims for",
+        long_help = "The effective DPI --auto-scale aims for. By default this is 135 for built-in monitors and 110 for external ones, like the display server's own defaults, since laptop panels are usually viewed from closer up."
+    )]
+    target_dpi: Option<f64>,
+
+    #[structopt(
+        long,
+        help = "One of 'logical' or 'physical'",
+        long_help = "One of 'logical' or 'physical'. In 'logical' mode a monitor's size in the layout is its resolution divided by its scale, while in 'physical' mode it is its resolution. Changing it moves the monitors so that they stay next to each other. Only available if the display server supports changing the layout mode."
+    )]
+    pub layout_mode: Option<LayoutMode>,
+
+    #[structopt(
+        long = "output",
+        value_name = "connector> <actions",
+        allow_hyphen_values = true,
+        min_values = 1,
+        help = "Modify another connector in the same transaction",
+        long_help = "Modify another connector in the same transaction, e.g. `modify --output DP-1 --mode 2560x1440 --output eDP-1 --rotate left`. Everything after --output, up to the next --output, applies to that connector. All changes are sent to the display server at once, and positions are worked out after every other change, starting with the absolute ones given with --pos. Options before the first --output have to be global ones such as --dry-run."
+    )]
+    outputs: Vec<String>,
+}
+
+impl CommandOptions {
+    /// Parses each --output group as if it were its own `modify` invocation, so that global flags
+    /// may also appear after the groups.
+    fn output_groups(&self) -> Result<Vec<CommandOptions>, structopt::clap::Error> {
+        if self.outputs.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        self.outputs
+            .split(|arg| arg == "--output")
+            .map(|group| {
+                CommandOptions::from_iter_safe(
+                    std::iter::once("modify").chain(group.iter().map(String::as_str)),
+                )
+            })
+            .collect()
+    }
 }
 
 #[derive(Debug)]
 pub enum Error {
     NotFound,
+    SelfReference,
+    Disabled,
+    TargetDisabled,
+    LastOutput,
+    NoModes,
+    LayoutModeUnsupported,
+    UnsupportedScale {
+        scale: f64,
+        connector: String,
+        mode: String,
+        supported: Vec<f64>,
+    },
+    NoCommonScale,
+    MirroringUnsupported,
+    NoSharedMode,
+    UnknownMode {
+        connector: String,
+        spec: String,
+        choices: Vec<String>,
+    },
+    Invalid(Vec<ValidationError>),
+    Selector(selector::Error),
+    Interrupted,
+    NoConnector,
 }
 
 impl std::fmt::Display for Error {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(
-            f,
-            "{}",
-            match &self {
-                Error::NotFound => "fatal: unable to find output.",
+        match &self {
+            Error::NotFound => write!(f, "fatal: unable to find output."),
+            Error::SelfReference => {
+                write!(f, "fatal: cannot position an output relative to itself.")
             }
-        )
+            Error::Disabled => write!(f, "fatal: output is disabled, use --auto to enable it."),
+            Error::TargetDisabled => write!(f, "fatal: the output to mirror is disabled."),
+            Error::MirroringUnsupported => {
+                write!(f, "fatal: the display server does not support mirroring.")
+            }
+            Error::NoSharedMode => {
+                write!(f, "fatal: the outputs do not share a common resolution.")
+            }
+            Error::LastOutput => write!(f, "fatal: cannot disable the last enabled output."),
+            Error::NoModes => write!(f, "fatal: output does not report any modes."),
+            Error::LayoutModeUnsupported => write!(
+                f,
+                "fatal: the display server does not support changing the layout mode."
+            ),
+            Error::UnsupportedScale {
+                scale,
+                connector,
+                mode,
+                supported,
+            } => {
+                writeln!(
+                    f,
+                    "fatal: scale {} is not supported by {} in mode {}.",
+                    scale, connector, mode
+                )?;
+                writeln!(
+                    f,
+                    "supported scales are: {}",
+                    supported
+                        .iter()
+                        .map(|scale| format!("{:.2}", scale))
+                        .collect::<Vec<String>>()
+                        .join(", ")
+                )?;
+                write!(f, "use --scale nearest:{} to pick the closest one.", scale)
+            }
+            Error::NoCommonScale => {
+                write!(f, "fatal: the monitors do not share any supported scale.")
+            }
+            Error::UnknownMode {
+                connector,
+                spec,
+                choices,
+            } => {
+                writeln!(f, "fatal: no mode matching '{}' on {}.", spec, connector)?;
+                writeln!(f, "valid modes are:")?;
+                for choice in choices.iter() {
+                    writeln!(f, "\t{}", choice)?;
+                }
+                write!(f, "or one of 'preferred', 'native' or 'highest-refresh'.")
+            }
+            Error::Invalid(errors) => {
+                writeln!(f, "fatal: the new configuration is invalid:")?;
+                for error in errors.iter() {
+                    writeln!(f, "\t{}", error)?;
+                }
+                write!(f, "use --force to apply it anyway.")
+            }
+            Error::Selector(error) => write!(f, "fatal: {}", error),
+            Error::Interrupted => write!(
+                f,
+                "fatal: interrupted before the changes were confirmed, they have been reverted."
+            ),
+            Error::NoConnector => write!(
+                f,
+                "fatal: options before the first --output don't apply to any connector, move them after an --output."
+            ),
+        }
     }
 }
 
@@ -113,75 +488,884 @@ pub fn handle(
     config: &DisplayConfig,
     proxy: &dbus::blocking::Proxy<&dbus::blocking::Connection>,
 ) -> Result<(), Box<dyn std::error::Error>> {
-    let (logical_monitor, physical_monitor) =
-        config.search(&opts.connector).ok_or(Error::NotFound)?;
+    let output_groups = opts.output_groups()?;
+    let groups: Vec<&CommandOptions> = std::iter::once(opts).chain(output_groups.iter()).collect();
+
+    let persistent = groups.iter().any(|group| group.persistent);
+    let dry_run = groups.iter().any(|group| group.dry_run);
+    let confirm = groups.iter().find_map(|group| group.confirm);
+    if groups
+        .iter()
+        .any(|group| group.connector.is_none() && !group.actions.is_empty())
+    {
+        return Err(Box::new(Error::NoConnector));
+    }
+    // Selectors such as `vendor:DEL` are resolved to the connector they match.
+    let outputs: Vec<(&str, &ActionOptions)> = groups
+        .iter()
+        .filter_map(|group| Some((group.connector.as_deref()?, &group.actions)))
+        .map(|(selector, actions)| Ok((config.select(selector)?.connector.as_str(), actions)))
+        .collect::<Result<_, selector::Error>>()
+        .map_err(Error::Selector)?;
+
+    // One config per logical monitor, so mirrored monitors stay together.
+    let mut all_configs: Vec<ApplyConfig> = config
+        .logical_monitors
+        .iter()
+        .map(|logical_monitor| ApplyConfig::from(logical_monitor, &config.monitors))
+        .collect();
+
+    let current_layout_mode = config.known_properties.layout_mode;
+    let layout_mode = match groups.iter().rev().find_map(|group| group.layout_mode) {
+        Some(layout_mode) if layout_mode != current_layout_mode => {
+            if !config.known_properties.supports_changing_layout_mode {
+                return Err(Box::new(Error::LayoutModeUnsupported));
+            }
+
+            // Logical monitor sizes are defined differently in each layout mode, so the monitors
+            // have to be moved to keep them next to each other.
+            println!("setting layout mode to {}", layout_mode);
+            let old_sizes = config_sizes(&all_configs, config, current_layout_mode);
+            let new_sizes = config_sizes(&all_configs, config, layout_mode);
+            relayout(&mut all_configs, &old_sizes, &new_sizes, Offsets::Scale);
+            Some(layout_mode)
+        }
+        _ => None,
+    };
+
+    // With several outputs, say which one each change applies to.
+    let prefix = |connector: &str| {
+        if outputs.len() > 1 {
+            format!("{}: ", connector)
+        } else {
+            String::new()
+        }
+    };
+
+    let mut changed = false;
+    for (connector, actions) in outputs.iter() {
+        let before = all_configs.clone();
+        changed |= modify_output(
+            connector,
+            actions,
+            config,
+            layout_mode.unwrap_or(current_layout_mode),
+            &mut all_configs,
+            &prefix(connector),
+        )?;
+
+        // A new mode, scale or rotation changes the monitor's size, so keep the monitors next to
+        // each other. Enabling, disabling or mirroring a monitor changes the logical monitors
+        // themselves, which leaves nothing to compare against.
+        if all_configs.len() == before.len() {
+            let layout_mode = layout_mode.unwrap_or(current_layout_mode);
+            let old_sizes = config_sizes(&before, config, layout_mode);
+            let new_sizes = config_sizes(&all_configs, config, layout_mode);
+            if old_sizes != new_sizes {
+                relayout(&mut all_configs, &old_sizes, &new_sizes, Offsets::Keep);
+            }
+        }
+    }
+
+    if groups.iter().any(|group| group.auto_scale) {
+        let explicit: Vec<&str> = outputs
+            .iter()
+            .filter(|(_, actions)| actions.scale.is_some())
+            .map(|(connector, _)| *connector)
+            .collect();
+
+        if config.known_properties.global_scale_required && !explicit.is_empty() {
+            println!("not picking scales automatically, --scale already sets the global scale");
+        } else {
+            let layout_mode = layout_mode.unwrap_or(current_layout_mode);
+            let old_sizes = config_sizes(&all_configs, config, layout_mode);
+            if autoscale::auto_scale(
+                &mut all_configs,
+                config,
+                groups.iter().find_map(|group| group.target_dpi),
+                &explicit,
+                config.known_properties.global_scale_required,
+            ) {
+                // Monitors change size along with their scale, so keep them next to each other.
+                let new_sizes = config_sizes(&all_configs, config, layout_mode);
+                relayout(&mut all_configs, &old_sizes, &new_sizes, Offsets::Keep);
+                changed = true;
+            }
+        }
+    }
+
+    // Relative positions depend on the final size and position of both monitors, so they are
+    // worked out once every other change has been made, and after every absolute position.
+    let (absolute, relative): (Vec<_>, Vec<_>) = outputs
+        .iter()
+        .partition(|(_, actions)| actions.pos.is_some());
+    for (connector, actions) in absolute.iter().chain(relative.iter()) {
+        changed |= position_output(
+            connector,
+            actions,
+            config,
+            layout_mode.unwrap_or(current_layout_mode),
+            &mut all_configs,
+            &prefix(connector),
+        )?;
+    }
+
+    if !changed && layout_mode.is_none() {
+        println!("no changes made.");
+        return Ok(());
+    }
+
+    if persistent {
+        println!("attempting to persist config to disk")
+    }
+
+    normalize_positions(&mut all_configs);
+
+    // Changes can move other monitors too, so show the whole layout's changes.
+    let diff = ConfigDiff::new(config, &all_configs);
+    if !diff.is_empty() {
+        println!("changes to the layout:");
+        print!("{}", diff);
+    }
+
+    // A dry run goes on to ask the display server, which has the final say.
+    let errors = config.validate_monitors_config(&all_configs, layout_mode);
+    if !errors.is_empty() {
+        if dry_run {
+            println!("the new configuration looks invalid:");
+        } else if groups.iter().any(|group| group.force) {
+            println!("the new configuration looks invalid, applying it anyway:");
+        } else {
+            return Err(Box::new(Error::Invalid(errors)));
+        }
+
+        for error in errors.iter() {
+            println!("\t{}", error);
+        }
+    }
+
+    if dry_run {
+        match config.verify_monitors_config(proxy, all_configs, layout_mode) {
+            Ok(()) => println!("the display server accepts the new configuration."),
+            Err(error) => println!(
+                "the display server rejects the new configuration: {}",
+                error.message().unwrap_or("no reason given")
+            ),
+        }
+        println!("dry run: no changes made.");
+        return Ok(());
+    }
+
+    let timeout = match confirm {
+        Some(timeout) => timeout,
+        None => {
+            return Ok(config.apply_monitors_config(proxy, all_configs, layout_mode, persistent)?)
+        }
+    };
+
+    // Catch Ctrl-C from here on, so the changes are never left applied without confirmation.
+    let pending = super::confirm::Pending::new()?;
+    config.apply_monitors_config(proxy, all_configs.clone(), layout_mode, false)?;
+    if pending.can_confirm_remotely() {
+        println!(
+            "keep this configuration? press any key or run `gnome-randr confirm` within {} seconds, otherwise it will be reverted.",
+            timeout
+        );
+    } else {
+        println!(
+            "keep this configuration? press any key within {} seconds, otherwise it will be reverted.",
+            timeout
+        );
+    }
+
+    let outcome = pending.wait(std::time::Duration::from_secs(timeout));
+
+    // Applying the changes bumped the serial, so fetch it again.
+    let current = DisplayConfig::get_current_state(proxy)?;
+    match outcome {
+        Outcome::Confirmed => {
+            println!("keeping the new configuration.");
+            if persistent {
+                current.apply_monitors_config(proxy, all_configs, layout_mode, true)?;
+            }
+        }
+        Outcome::TimedOut | Outcome::Interrupted => {
+            if outcome == Outcome::TimedOut {
+                println!("no confirmation received, reverting.");
+            } else {
+                println!("interrupted, reverting.");
+            }
+            current.apply_monitors_config(
+                proxy,
+                config
+                    .logical_monitors
+                    .iter()
+                    .map(|logical_monitor| ApplyConfig::from(logical_monitor, &config.monitors))
+                    .collect(),
+                layout_mode.map(|_| current_layout_mode),
+                false,
+            )?;
+        }
+    }
+
+    // Only now let a second Ctrl-C stop the process.
+    drop(pending);
+    if outcome == Outcome::Interrupted {
+        return Err(Box::new(Error::Interrupted));
+    }
+
+    Ok(())
+}
+
+/// Applies the actions for a single output (other than positioning) to the configs, printing a
+/// description of each. Returns whether anything changed.
+fn modify_output<'a>(
+    connector: &str,
+    opts: &ActionOptions,
+    config: &'a DisplayConfig,
+    layout_mode: LayoutMode,
+    all_configs: &mut Vec<ApplyConfig<'a>>,
+    prefix: &str,
+) -> Result<bool, Box<dyn std::error::Error>> {
+    let physical_monitor = config.search_physical(connector).ok_or(Error::NotFound)?;
+
+    if opts.off {
+        return disable(physical_monitor, all_configs, prefix);
+    }
 
     let mut actions = Vec::<Box<dyn Action>>::new();
-    let primary_is_changing = opts.actions.primary;
+    let is_enabled = find_group(all_configs, connector).is_some();
+
+    let index = if let Some(target) = &opts.same_as {
+        if !config.known_properties.supports_mirroring {
+            return Err(Box::new(Error::MirroringUnsupported));
+        }
 
-    if let Some(rotation) = &opts.actions.rotation {
+        let target_monitor = config.select(target).map_err(Error::Selector)?;
+        if target_monitor.connector == physical_monitor.connector {
+            return Err(Box::new(Error::SelfReference));
+        }
+
+        // Leave the current logical monitor before joining the target's. If that empties it, the
+        // merged logical monitor takes over its primary status.
+        let was_primary = detach(all_configs, connector) == Some(true);
+
+        let index =
+            find_group(all_configs, &target_monitor.connector).ok_or(Error::TargetDisabled)?;
+        actions.push(Box::new(MirrorAction::new(
+            &target_monitor.connector,
+            &all_configs[index],
+            physical_monitor,
+            config,
+        )?));
+
+        if was_primary {
+            println!("{}moving primary to {}", prefix, target_monitor.connector);
+            for (i, apply_config) in all_configs.iter_mut().enumerate() {
+                apply_config.primary = i == index;
+            }
+        }
+        index
+    } else {
+        // A disabled monitor gets a brand new logical monitor, so only --auto can bring it back.
+        match (find_group(all_configs, connector), opts.auto) {
+            (Some(index), _) => index,
+            (None, true) => {
+                let apply_config =
+                    enabled_config(config, physical_monitor, layout_mode, all_configs)?;
+                println!(
+                    "{}enabling output at {}x{} with mode {}",
+                    prefix,
+                    apply_config.x_pos,
+                    apply_config.y_pos,
+                    apply_config.monitors[0].mode_id
+                );
+                all_configs.push(apply_config);
+                all_configs.len() - 1
+            }
+            (None, false) => return Err(Box::new(Error::Disabled)),
+        }
+    };
+
+    if let Some(rotation) = &opts.rotation {
         actions.push(Box::new(RotationAction {
             rotation: *rotation,
         }));
     }
 
-    if let Some(mode_id) = &opts.actions.mode {
-        actions.push(Box::new(ModeAction { mode: mode_id }))
+    // --auto switches an enabled monitor back to its preferred mode.
+    let mode_selector = opts
+        .mode
+        .clone()
+        .or_else(|| (opts.auto && is_enabled).then_some(ModeSelector::Preferred));
+    if mode_selector.is_some() || opts.rate.is_some() {
+        let mode = resolve_mode(
+            physical_monitor,
+            mode_selector.as_ref(),
+            opts.rate,
+            &all_configs[index],
+        )?;
+        actions.push(Box::new(ModeAction { mode: &mode.id }))
     }
 
-    if opts.actions.primary {
+    if opts.primary {
         actions.push(Box::new(PrimaryAction {}));
     }
 
-    if let Some(scale) = &opts.actions.scale {
-        actions.push(Box::new(ScaleAction { scale: *scale }))
-    }
+    let global_scale_required = config.known_properties.global_scale_required;
+    let mut new_scale = None;
+    if let Some(requested) = opts.scale {
+        // The scale has to be supported by the mode the monitor ends up with, so preview the
+        // actions so far.
+        let mut preview = all_configs[index].clone();
+        for action in actions.iter() {
+            action.apply(&mut preview, physical_monitor);
+        }
 
-    if actions.is_empty() {
-        println!("no changes made.");
-        return Ok(());
-    }
+        let groups: Vec<&ApplyConfig> = if global_scale_required {
+            all_configs
+                .iter()
+                .enumerate()
+                .map(|(i, apply_config)| if i == index { &preview } else { apply_config })
+                .collect()
+        } else {
+            vec![&preview]
+        };
 
-    let mut apply_config = ApplyConfig::from(logical_monitor, physical_monitor);
+        let scale = resolve_scale(requested, &groups, config)?;
+        new_scale = Some(scale);
+        actions.push(Box::new(ScaleAction { scale }))
+    } else if opts.same_as.is_some() {
+        // The shared mode may not support the scale the target's logical monitor already uses.
+        let mut preview = all_configs[index].clone();
+        for action in actions.iter() {
+            action.apply(&mut preview, physical_monitor);
+        }
+        resolve_scale(Scale::Exact(preview.scale), &[&preview], config)?;
+    }
 
-    if opts.persistent {
-        println!("attempting to persist config to disk")
+    if opts.primary {
+        for apply_config in all_configs.iter_mut() {
+            apply_config.primary = false;
+        }
     }
 
     for action in actions.iter() {
-        println!("{}", &action);
-        action.apply(&mut apply_config, physical_monitor);
+        println!("{}{}", prefix, &action);
+        action.apply(&mut all_configs[index], physical_monitor);
     }
 
-    if opts.dry_run {
-        println!("dry run: no changes made.");
-        return Ok(());
+    if let (Some(scale), true) = (new_scale, global_scale_required) {
+        println!(
+            "{}setting scale of every monitor to {} (the display server requires a global scale)",
+            prefix, scale
+        );
+        for apply_config in all_configs.iter_mut() {
+            apply_config.scale = scale;
+        }
     }
 
-    let all_configs = config
-        .monitors
+    Ok(!actions.is_empty() || !is_enabled)
+}
+
+/// Moves a single output to its absolute or relative position. Returns whether anything changed.
+fn position_output<'a>(
+    connector: &str,
+    opts: &ActionOptions,
+    config: &'a DisplayConfig,
+    layout_mode: LayoutMode,
+    all_configs: &mut [ApplyConfig<'a>],
+    prefix: &str,
+) -> Result<bool, Box<dyn std::error::Error>> {
+    let physical_monitor = config.search_physical(connector).ok_or(Error::NotFound)?;
+
+    let action = if let Some(position) = &opts.pos {
+        PositionAction {
+            placement: Placement::Absolute(*position),
+            layout_mode,
+        }
+    } else if let Some((side, target)) = opts.relative_to() {
+        let target_monitor = config.select(target).map_err(Error::Selector)?;
+        if target_monitor.connector == physical_monitor.connector {
+            return Err(Box::new(Error::SelfReference));
+        }
+        let target_index =
+            find_group(all_configs, &target_monitor.connector).ok_or(Error::TargetDisabled)?;
+
+        PositionAction {
+            placement: Placement::Relative {
+                side,
+                target: all_configs[target_index].clone(),
+                target_monitor,
+                align: opts.align,
+            },
+            layout_mode,
+        }
+    } else {
+        return Ok(false);
+    };
+
+    let index = find_group(all_configs, connector).ok_or(Error::Disabled)?;
+
+    println!("{}{}", prefix, &action);
+    action.apply(&mut all_configs[index], physical_monitor);
+
+    Ok(true)
+}
+
+/// Turns the --mode and --rate options into one of the monitor's modes. Without --mode, the rate
+/// applies to the resolution the monitor is (about to be) using.
+fn resolve_mode<'a>(
+    physical_monitor: &'a PhysicalMonitor,
+    selector: Option<&ModeSelector>,
+    refresh_rate: Option<f64>,
+    apply_config: &ApplyConfig,
+) -> Result<&'a Mode, Error> {
+    let unknown_mode = |spec: String| Error::UnknownMode {
+        connector: physical_monitor.connector.clone(),
+        spec,
+        choices: physical_monitor
+            .resolutions()
+            .iter()
+            .map(|((width, height), modes)| {
+                format!(
+                    "{}x{} @ {}",
+                    width,
+                    height,
+                    modes
+                        .iter()
+                        .map(|mode| format!("{:.2}", mode.refresh_rate))
+                        .collect::<Vec<String>>()
+                        .join(", ")
+                )
+            })
+            .collect(),
+    };
+
+    let mode = match selector {
+        Some(selector) => physical_monitor
+            .select_mode(selector)
+            .ok_or_else(|| unknown_mode(selector.to_string()))?,
+        None => apply_config
+            .monitors
+            .iter()
+            .find(|monitor| monitor.connector == physical_monitor.connector)
+            .and_then(|monitor| {
+                physical_monitor
+                    .modes
+                    .iter()
+                    .find(|mode| mode.id == monitor.mode_id)
+            })
+            .ok_or(Error::NoModes)?,
+    };
+
+    match refresh_rate {
+        Some(refresh_rate) => physical_monitor
+            .find_mode(mode.width, mode.height, Some(refresh_rate))
+            .ok_or_else(|| {
+                unknown_mode(format!("{}x{}@{}", mode.width, mode.height, refresh_rate))
+            }),
+        None => Ok(mode),
+    }
+}
+
+/// Checks the requested scale against the modes of every monitor in the given logical monitor
+/// configs, returning the exact value the display server advertised.
+fn resolve_scale(
+    requested: Scale,
+    groups: &[&ApplyConfig],
+    config: &DisplayConfig,
+) -> Result<f64, Error> {
+    let modes: Vec<(&str, &Mode)> = groups
         .iter()
+        .flat_map(|apply_config| apply_config.monitors.iter())
         .filter_map(|monitor| {
-            if monitor.connector == opts.connector {
-                return Some(apply_config.clone());
+            let physical_monitor = config.search_physical(monitor.connector)?;
+            let mode = physical_monitor
+                .modes
+                .iter()
+                .find(|mode| mode.id == monitor.mode_id)?;
+            Some((physical_monitor.connector.as_str(), mode))
+        })
+        .collect();
+
+    let first_mode = match modes.first() {
+        Some((_, mode)) => mode,
+        None => return Err(Error::NoModes),
+    };
+
+    match requested {
+        Scale::Exact(scale) => {
+            if let Some((connector, mode)) = modes
+                .iter()
+                .find(|(_, mode)| mode.find_scale(scale).is_none())
+            {
+                return Err(Error::UnsupportedScale {
+                    scale,
+                    connector: connector.to_string(),
+                    mode: mode.id.clone(),
+                    supported: mode.supported_scales.clone(),
+                });
             }
+            first_mode.find_scale(scale).ok_or(Error::NoCommonScale)
+        }
+        Scale::Nearest(scale) => {
+            let others: Vec<&Mode> = modes[1..].iter().map(|(_, mode)| *mode).collect();
+            first_mode
+                .nearest_common_scale(scale, &others)
+                .ok_or(Error::NoCommonScale)
+        }
+    }
+}
+
+/// The index of the logical monitor config that contains the given connector.
+fn find_group(configs: &[ApplyConfig], connector: &str) -> Option<usize> {
+    configs.iter().position(|apply_config| {
+        apply_config
+            .monitors
+            .iter()
+            .any(|monitor| monitor.connector == connector)
+    })
+}
 
-            let (logical_monitor, _) = match config.search(&monitor.connector) {
-                Some(monitors) => monitors,
-                None => return None,
-            };
+/// Removes the connector from its logical monitor config, dropping the logical monitor if no other
+/// monitors display it. Returns whether the dropped logical monitor was primary, or `None` if it
+/// was kept (or the connector wasn't enabled to begin with).
+fn detach(configs: &mut Vec<ApplyConfig>, connector: &str) -> Option<bool> {
+    let index = find_group(configs, connector)?;
+    configs[index]
+        .monitors
+        .retain(|monitor| monitor.connector != connector);
 
-            let mut apply_config = ApplyConfig::from(logical_monitor, monitor);
+    if configs[index].monitors.is_empty() {
+        Some(configs.remove(index).primary)
+    } else {
+        None
+    }
+}
 
-            if primary_is_changing {
-                apply_config.primary = false;
-            }
+/// Removes the monitor from the configuration entirely, which is how Mutter disables an output.
+fn disable(
+    physical_monitor: &PhysicalMonitor,
+    all_configs: &mut Vec<ApplyConfig>,
+    prefix: &str,
+) -> Result<bool, Box<dyn std::error::Error>> {
+    if find_group(all_configs, &physical_monitor.connector).is_none() {
+        println!(
+            "{}{} is already disabled.",
+            prefix, physical_monitor.connector
+        );
+        return Ok(false);
+    }
+
+    let was_primary = detach(all_configs, &physical_monitor.connector);
+
+    if all_configs.is_empty() {
+        return Err(Box::new(Error::LastOutput));
+    }
+
+    println!("{}disabling output", prefix);
+
+    if was_primary == Some(true) {
+        let new_primary = &mut all_configs[0];
+        new_primary.primary = true;
+        println!(
+            "{}setting {} as primary",
+            prefix, new_primary.monitors[0].connector
+        );
+    }
+
+    Ok(true)
+}
+
+/// The size of a logical monitor config, using the first physical monitor displaying it (all of
+/// them share a resolution).
+fn config_size(
+    apply_config: &ApplyConfig,
+    config: &DisplayConfig,
+    layout_mode: LayoutMode,
+) -> (i32, i32) {
+    apply_config
+        .logical_size(&config.monitors, layout_mode)
+        .unwrap_or((0, 0))
+}
+
+/// Builds a new logical monitor for a disabled physical monitor, using its preferred mode and
+/// placing it to the right of the existing layout.
+fn enabled_config<'a>(
+    config: &'a DisplayConfig,
+    physical_monitor: &'a PhysicalMonitor,
+    layout_mode: LayoutMode,
+    all_configs: &[ApplyConfig],
+) -> Result<ApplyConfig<'a>, Error> {
+    let mode = physical_monitor.preferred_mode().ok_or(Error::NoModes)?;
 
-            Some(apply_config)
+    let x_pos = all_configs
+        .iter()
+        .map(|apply_config| apply_config.x_pos + config_size(apply_config, config, layout_mode).0)
+        .max()
+        .unwrap_or(0);
+
+    Ok(ApplyConfig {
+        x_pos,
+        y_pos: 0,
+        scale: mode.preferred_scale,
+        transform: Transform::NORMAL.bits(),
+        primary: false,
+        monitors: vec![ApplyMonitor {
+            connector: &physical_monitor.connector,
+            mode_id: &mode.id,
+        }],
+    })
+}
+
+fn config_sizes(
+    configs: &[ApplyConfig],
+    config: &DisplayConfig,
+    layout_mode: LayoutMode,
+) -> Vec<(i32, i32)> {
+    configs
+        .iter()
+        .map(|apply_config| config_size(apply_config, config, layout_mode))
+        .collect()
+}
+
+/// How `relayout` moves a monitor along the edge it shares with a monitor above it or to its left.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Offsets {
+    /// Keep the offset, e.g. when a single monitor changes its mode, scale or rotation.
+    Keep,
+    /// Scale the offset along with the other monitor, e.g. when switching the layout mode
+    /// changes the size of every monitor at once.
+    Scale,
+}
+
+/// Moves the logical monitors so they stay next to each other when their sizes change, e.g. from
+/// one layout mode to the other. A monitor that touched the right (or bottom) edge of another is
+/// moved along with that edge, and one that only shares an edge with a monitor above it (or to its
+/// left) moves its offset from that monitor as given by `offsets`. Every other monitor stays where
+/// it is.
+fn relayout(
+    configs: &mut [ApplyConfig],
+    old_sizes: &[(i32, i32)],
+    new_sizes: &[(i32, i32)],
+    offsets: Offsets,
+) {
+    let old_rects: Vec<(i32, i32, i32, i32)> = configs
+        .iter()
+        .zip(old_sizes.iter())
+        .map(|(apply_config, (width, height))| {
+            (apply_config.x_pos, apply_config.y_pos, *width, *height)
         })
         .collect();
 
-    config.apply_monitors_config(proxy, all_configs, opts.persistent)?;
+    let overlaps = |a_start: i32, a_len: i32, b_start: i32, b_len: i32| {
+        a_start < b_start + b_len && b_start < a_start + a_len
+    };
+    let offset = |offset: i32, old_len: i32, new_len: i32| match offsets {
+        Offsets::Keep => offset,
+        Offsets::Scale => (offset as f64 * new_len as f64 / old_len as f64).round() as i32,
+    };
+    // Whether `j` touched the left edge of `i`, and whether it touched the top edge of `i`.
+    let left_of = |i: usize, j: usize| {
+        let (x, y, _, height) = old_rects[i];
+        let (other_x, other_y, other_width, other_height) = old_rects[j];
+        other_x + other_width == x && overlaps(y, height, other_y, other_height)
+    };
+    let above = |i: usize, j: usize| {
+        let (x, y, width, _) = old_rects[i];
+        let (other_x, other_y, other_width, other_height) = old_rects[j];
+        other_y + other_height == y && overlaps(x, width, other_x, other_width)
+    };
 
-    Ok(())
+    // Monitors only follow the ones to their left or above them, so every position has settled
+    // after one pass per monitor.
+    let indices = 0..configs.len();
+    let mut positions: Vec<(i32, i32)> = old_rects.iter().map(|(x, y, _, _)| (*x, *y)).collect();
+    for _ in indices.clone() {
+        positions = indices
+            .clone()
+            .map(|i| {
+                let (x, y, _, _) = old_rects[i];
+                let new_x = indices
+                    .clone()
+                    .filter(|&j| left_of(i, j))
+                    .map(|j| positions[j].0 + new_sizes[j].0)
+                    .max()
+                    .or_else(|| {
+                        let j = indices.clone().find(|&j| above(i, j))?;
+                        Some(
+                            positions[j].0
+                                + offset(x - old_rects[j].0, old_rects[j].2, new_sizes[j].0),
+                        )
+                    })
+                    .unwrap_or(x);
+                let new_y = indices
+                    .clone()
+                    .filter(|&j| above(i, j))
+                    .map(|j| positions[j].1 + new_sizes[j].1)
+                    .max()
+                    .or_else(|| {
+                        let j = indices.clone().find(|&j| left_of(i, j))?;
+                        Some(
+                            positions[j].1
+                                + offset(y - old_rects[j].1, old_rects[j].3, new_sizes[j].1),
+                        )
+                    })
+                    .unwrap_or(y);
+                (new_x, new_y)
+            })
+            .collect();
+    }
+
+    for (apply_config, (x_pos, y_pos)) in configs.iter_mut().zip(positions) {
+        apply_config.x_pos = x_pos;
+        apply_config.y_pos = y_pos;
+    }
+}
+
+/// Mutter requires the layout to start at 0x0, so shift every logical monitor if a change moved
+/// one of them into negative coordinates (or away from the origin).
+fn normalize_positions(configs: &mut [ApplyConfig]) {
+    let min_x = configs.iter().map(|config| config.x_pos).min().unwrap_or(0);
+    let min_y = configs.iter().map(|config| config.y_pos).min().unwrap_or(0);
+
+    for config in configs.iter_mut() {
+        config.x_pos -= min_x;
+        config.y_pos -= min_y;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(x_pos: i32, y_pos: i32) -> ApplyConfig<'static> {
+        ApplyConfig {
+            x_pos,
+            y_pos,
+            scale: 1.0,
+            transform: 0,
+            primary: false,
+            monitors: Vec::new(),
+        }
+    }
+
+    fn positions(configs: &[ApplyConfig]) -> Vec<(i32, i32)> {
+        configs
+            .iter()
+            .map(|apply_config| (apply_config.x_pos, apply_config.y_pos))
+            .collect()
+    }
+
+    #[test]
+    fn relayout_keeps_a_row_together() {
+        // A laptop at scale 2 next to a monitor, with the laptop going to scale 1.5.
+        let mut configs = vec![at(0, 0), at(1440, 0)];
+        relayout(
+            &mut configs,
+            &[(1440, 900), (2560, 1440)],
+            &[(1920, 1200), (2560, 1440)],
+            Offsets::Keep,
+        );
+        assert_eq!(positions(&configs), vec![(0, 0), (1920, 0)]);
+    }
+
+    #[test]
+    fn relayout_keeps_a_column_together() {
+        // A monitor above the laptop, rotated so that it gets taller.
+        let mut configs = vec![at(0, 1440), at(0, 0)];
+        relayout(
+            &mut configs,
+            &[(1440, 900), (2560, 1440)],
+            &[(1440, 900), (1440, 2560)],
+            Offsets::Keep,
+        );
+        assert_eq!(positions(&configs), vec![(0, 2560), (0, 0)]);
+    }
+
+    #[test]
+    fn relayout_packs_against_every_monitor_to_the_left() {
+        // Two stacked monitors with a third to the right of both; the bottom one grows.
+        let mut configs = vec![at(0, 0), at(0, 1080), at(1920, 0)];
+        relayout(
+            &mut configs,
+            &[(1920, 1080), (1920, 1080), (2560, 1440)],
+            &[(1920, 1080), (2560, 1440), (2560, 1440)],
+            Offsets::Keep,
+        );
+        assert_eq!(positions(&configs), vec![(0, 0), (0, 1080), (2560, 0)]);
+    }
+
+    #[test]
+    fn relayout_closes_gaps_when_shrinking() {
+        let mut configs = vec![at(0, 0), at(2560, 0)];
+        relayout(
+            &mut configs,
+            &[(2560, 1440), (1920, 1080)],
+            &[(1280, 720), (1920, 1080)],
+            Offsets::Keep,
+        );
+        assert_eq!(positions(&configs), vec![(0, 0), (1280, 0)]);
+    }
+
+    #[test]
+    fn relayout_keeps_offsets_along_the_shared_edge() {
+        // The laptop bottom-aligned to the right of a monitor, going from scale 2 to 1.5.
+        let mut configs = vec![at(0, 0), at(2560, 540)];
+        relayout(
+            &mut configs,
+            &[(2560, 1440), (1440, 900)],
+            &[(2560, 1440), (1920, 1200)],
+            Offsets::Keep,
+        );
+        assert_eq!(positions(&configs), vec![(0, 0), (2560, 540)]);
+
+        // The laptop centred below a monitor that is rotated.
+        let mut configs = vec![at(0, 0), at(560, 1440)];
+        relayout(
+            &mut configs,
+            &[(2560, 1440), (1440, 900)],
+            &[(1440, 2560), (1440, 900)],
+            Offsets::Keep,
+        );
+        assert_eq!(positions(&configs), vec![(0, 0), (560, 2560)]);
+    }
+
+    #[test]
+    fn relayout_moves_monitors_along_with_their_neighbours() {
+        // A monitor above the laptop and a projector to the laptop's right, lower down. Only the
+        // laptop touches the monitor, but the projector has to follow it.
+        let mut configs = vec![at(0, 0), at(0, 1440), at(1440, 1620)];
+        relayout(
+            &mut configs,
+            &[(2560, 1440), (1440, 900), (1920, 1080)],
+            &[(1440, 2560), (1440, 900), (1920, 1080)],
+            Offsets::Keep,
+        );
+        assert_eq!(positions(&configs), vec![(0, 0), (0, 2560), (1440, 2740)]);
+    }
+
+    #[test]
+    fn relayout_scales_offsets_with_the_layout_mode() {
+        // A monitor halfway down the laptop, going from the logical to the physical layout mode.
+        let mut configs = vec![at(0, 0), at(1440, 450)];
+        relayout(
+            &mut configs,
+            &[(1440, 900), (2560, 1440)],
+            &[(2880, 1800), (2560, 1440)],
+            Offsets::Scale,
+        );
+        assert_eq!(positions(&configs), vec![(0, 0), (2880, 900)]);
+
+        // A monitor below the laptop, starting halfway across it.
+        let mut configs = vec![at(0, 0), at(720, 900)];
+        relayout(
+            &mut configs,
+            &[(1440, 900), (2560, 1440)],
+            &[(2880, 1800), (2560, 1440)],
+            Offsets::Scale,
+        );
+        assert_eq!(positions(&configs), vec![(0, 0), (1440, 1800)]);
+    }
 }
@@ -0,0 +1,689 @@
+use std::path::PathBuf;
+
+use gnome_randr::{
+    display_config::{
+        logical_monitor::Transform, physical_monitor::PhysicalMonitor, validation::ValidationError,
+        ApplyConfig, ApplyMonitor, LayoutMode,
+    },
+    DisplayConfig,
+};
+use serde::{Deserialize, Serialize};
+use structopt::StructOpt;
+
+#[derive(StructOpt)]
+pub enum CommandOptions {
+    #[structopt(about = "Save the current configuration as a named profile")]
+    Save { name: String },
+    #[structopt(
+        about = "List the saved profiles, marking the one matching the current configuration"
+    )]
+    List,
+    #[structopt(about = "Show the contents of a saved profile")]
+    Show { name: String },
+    #[structopt(about = "Apply a saved profile")]
+    Apply {
+        name: String,
+
+        #[structopt(
+            short,
+            long,
+            help = "Attempt to replicate this configuration the next time this HW layout appears"
+        )]
+        persistent: bool,
+
+        #[structopt(long, help = "List changes without actually applying them")]
+        dry_run: bool,
+
+        #[structopt(
+            long,
+            help = "Apply the profile even if it looks invalid for the connected monitors",
+            long_help = "Apply the profile even if it looks invalid for the connected monitors, e.g. because they overlap at the modes the profile uses. The problems are still listed, and the display server may reject the configuration anyway."
+        )]
+        force: bool,
+    },
+    #[structopt(about = "Delete a saved profile")]
+    Delete { name: String },
+    #[structopt(about = "Print the name of the saved profile matching the current configuration")]
+    Current,
+}
+
+#[derive(Debug)]
+pub enum Error {
+    NoConfigDir,
+    InvalidName(String),
+    NotFound(String),
+    Corrupt(String, serde_json::Error),
+    MissingMonitor(String),
+    MissingMode(String, String),
+    LayoutModeUnsupported,
+    Invalid(Vec<ValidationError>),
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self {
+            Error::NoConfigDir => write!(
+                f,
+                "fatal: unable to find the config directory, set $XDG_CONFIG_HOME or $HOME."
+            ),
+            Error::InvalidName(name) => write!(f, "fatal: '{}' is not a valid profile name.", name),
+            Error::NotFound(name) => write!(f, "fatal: no profile named '{}'.", name),
+            Error::Corrupt(name, error) => {
+                write!(f, "fatal: unable to read profile '{}': {}", name, error)
+            }
+            Error::MissingMonitor(monitor) => {
+                write!(f, "fatal: monitor {} is not connected.", monitor)
+            }
+            Error::MissingMode(monitor, mode) => {
+                write!(f, "fatal: monitor {} does not support mode {}.", monitor, mode)
+            }
+            Error::LayoutModeUnsupported => write!(
+                f,
+                "fatal: the profile uses a different layout mode, and the display server does not support changing it."
+            ),
+            Error::Invalid(errors) => {
+                writeln!(f, "fatal: the profile is invalid for the connected monitors:")?;
+                for error in errors.iter() {
+                    writeln!(f, "\t{}", error)?;
+                }
+                write!(f, "use --force to apply it anyway.")
+            }
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// A monitor is identified by its EDID rather than its connector, which changes depending on which
+/// port (or dock) it is plugged into.
+#[derive(Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub struct MonitorId {
+    pub vendor: String,
+    pub product: String,
+    pub serial: String,
+}
+
+impl MonitorId {
+    pub fn from(physical_monitor: &PhysicalMonitor) -> MonitorId {
+        MonitorId {
+            vendor: physical_monitor.vendor.clone(),
+            product: physical_monitor.product.clone(),
+            serial: physical_monitor.serial.clone(),
+        }
+    }
+
+    pub fn matches(&self, physical_monitor: &PhysicalMonitor) -> bool {
+        self.vendor == physical_monitor.vendor
+            && self.product == physical_monitor.product
+            && self.serial == physical_monitor.serial
+    }
+}
+
+impl std::fmt::Display for MonitorId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {} {}", self.vendor, self.product, self.serial)
+    }
+}
+
+/// Mode IDs are only valid for the current session, so modes are stored by resolution and rate.
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(rename_all = "kebab-case")]
+pub struct ProfileMode {
+    pub width: i32,
+    pub height: i32,
+    pub refresh_rate: f64,
+}
+
+impl std::fmt::Display for ProfileMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}x{}@{:.2}", self.width, self.height, self.refresh_rate)
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(rename_all = "kebab-case")]
+pub struct ProfileMonitor {
+    #[serde(flatten)]
+    pub id: MonitorId,
+    // the connector the monitor was on when the profile was saved, for reference only
+    pub connector: String,
+    pub mode: ProfileMode,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(rename_all = "kebab-case")]
+pub struct ProfileLogicalMonitor {
+    pub x: i32,
+    pub y: i32,
+    pub scale: f64,
+    pub transform: u32,
+    pub primary: bool,
+    pub monitors: Vec<ProfileMonitor>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(rename_all = "kebab-case")]
+pub struct Profile {
+    #[serde(with = "layout_mode_name")]
+    pub layout_mode: LayoutMode,
+    pub logical_monitors: Vec<ProfileLogicalMonitor>,
+    // monitors that were connected but turned off, so the daemon can tell setups apart
+    #[serde(default)]
+    pub disabled: Vec<MonitorId>,
+}
+
+/// The layout mode is stored by name, as given to `modify --layout-mode`.
+mod layout_mode_name {
+    use gnome_randr::display_config::LayoutMode;
+    use serde::{de, Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(
+        layout_mode: &LayoutMode,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(layout_mode)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<LayoutMode, D::Error> {
+        let name = String::deserialize(deserializer)?;
+        name.parse().map_err(|_| {
+            de::Error::invalid_value(de::Unexpected::Str(&name), &"'logical' or 'physical'")
+        })
+    }
+}
+
+/// `$XDG_CONFIG_HOME/gnome-randr`, falling back to `~/.config/gnome-randr`.
+pub fn config_dir() -> Result<PathBuf, Error> {
+    std::env::var_os("XDG_CONFIG_HOME")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))
+        .map(|dir| dir.join("gnome-randr"))
+        .ok_or(Error::NoConfigDir)
+}
+
+fn profiles_dir() -> Result<PathBuf, Error> {
+    Ok(config_dir()?.join("profiles"))
+}
+
+fn profile_path(name: &str) -> Result<PathBuf, Error> {
+    if name.is_empty() || name.starts_with('.') || name.contains('/') {
+        return Err(Error::InvalidName(name.to_string()));
+    }
+
+    Ok(profiles_dir()?.join(format!("{}.json", name)))
+}
+
+/// Whether every item in `a` can be paired with a different item in `b`, and vice versa.
+fn pair_up<T>(a: &[T], b: &[T], same: impl Fn(&T, &T) -> bool) -> bool {
+    let mut used = vec![false; b.len()];
+    a.len() == b.len()
+        && a.iter().all(|item| {
+            match (0..b.len()).find(|&index| !used[index] && same(item, &b[index])) {
+                Some(index) => {
+                    used[index] = true;
+                    true
+                }
+                None => false,
+            }
+        })
+}
+
+impl Profile {
+    pub fn from(config: &DisplayConfig) -> Profile {
+        Profile {
+            layout_mode: config.known_properties.layout_mode,
+            logical_monitors: config
+                .logical_monitors
+                .iter()
+                .map(|logical_monitor| ProfileLogicalMonitor {
+                    x: logical_monitor.x,
+                    y: logical_monitor.y,
+                    scale: logical_monitor.scale,
+                    transform: logical_monitor.transform.bits(),
+                    primary: logical_monitor.primary,
+                    monitors: logical_monitor
+                        .monitors
+                        .iter()
+                        .filter_map(|monitor| config.search_physical(&monitor.connector))
+                        .filter_map(|physical_monitor| {
+                            let mode = physical_monitor.current_mode()?;
+                            Some(ProfileMonitor {
+                                id: MonitorId::from(physical_monitor),
+                                connector: physical_monitor.connector.clone(),
+                                mode: ProfileMode {
+                                    width: mode.width,
+                                    height: mode.height,
+                                    refresh_rate: mode.refresh_rate,
+                                },
+                            })
+                        })
+                        .collect(),
+                })
+                .collect(),
+            disabled: config
+                .monitors
+                .iter()
+                .filter(|physical_monitor| {
+                    config.search_logical(&physical_monitor.connector).is_none()
+                })
+                .map(MonitorId::from)
+                .collect(),
+        }
+    }
+
+    pub fn load(name: &str) -> Result<Profile, Error> {
+        let path = profile_path(name)?;
+        let contents =
+            std::fs::read_to_string(&path).map_err(|_| Error::NotFound(name.to_string()))?;
+        serde_json::from_str(&contents).map_err(|error| Error::Corrupt(name.to_string(), error))
+    }
+
+    pub fn save(&self, name: &str) -> Result<PathBuf, Box<dyn std::error::Error>> {
+        let path = profile_path(name)?;
+        std::fs::create_dir_all(profiles_dir()?)?;
+        std::fs::write(&path, serde_json::to_string_pretty(self)?)?;
+        Ok(path)
+    }
+
+    /// The names of all saved profiles, sorted.
+    pub fn list() -> Result<Vec<String>, Box<dyn std::error::Error>> {
+        let dir = profiles_dir()?;
+        if !dir.exists() {
+            return Ok(Vec::new());
+        }
+
+        let mut names: Vec<String> = std::fs::read_dir(dir)?
+            .filter_map(|entry| {
+                let path = entry.ok()?.path();
+                if path.extension()? != "json" {
+                    return None;
+                }
+                Some(path.file_stem()?.to_str()?.to_string())
+            })
+            .collect();
+        names.sort();
+        Ok(names)
+    }
+
+    /// Every monitor that was connected when the profile was saved, including disabled ones.
+    pub fn monitor_ids(&self) -> Vec<&MonitorId> {
+        self.logical_monitors
+            .iter()
+            .flat_map(|logical_monitor| logical_monitor.monitors.iter())
+            .map(|monitor| &monitor.id)
+            .chain(self.disabled.iter())
+            .collect()
+    }
+
+    /// Whether the profile describes the same layout, ignoring connector names and the order
+    /// monitors are listed in.
+    pub fn matches(&self, other: &Profile) -> bool {
+        fn same_monitor(a: &ProfileMonitor, b: &ProfileMonitor) -> bool {
+            a.id == b.id
+                && a.mode.width == b.mode.width
+                && a.mode.height == b.mode.height
+                && (a.mode.refresh_rate - b.mode.refresh_rate).abs() < 0.01
+        }
+
+        fn same_logical_monitor(a: &ProfileLogicalMonitor, b: &ProfileLogicalMonitor) -> bool {
+            a.x == b.x
+                && a.y == b.y
+                && (a.scale - b.scale).abs() < 0.001
+                && a.transform == b.transform
+                && a.primary == b.primary
+                && pair_up(&a.monitors, &b.monitors, same_monitor)
+        }
+
+        self.layout_mode == other.layout_mode
+            && pair_up(
+                &self.logical_monitors,
+                &other.logical_monitors,
+                same_logical_monitor,
+            )
+    }
+
+    /// Turns the profile into configs for the connected monitors, matching them by vendor, product
+    /// and serial. Each connected monitor is used at most once, so identical monitors (or ones
+    /// without a serial) are told apart by the connector they were on when the profile was saved.
+    pub fn to_apply_configs<'a>(
+        &self,
+        config: &'a DisplayConfig,
+    ) -> Result<Vec<ApplyConfig<'a>>, Error> {
+        let saved: Vec<&ProfileMonitor> = self
+            .logical_monitors
+            .iter()
+            .flat_map(|logical_monitor| logical_monitor.monitors.iter())
+            .collect();
+
+        // Prefer the saved connector, then fall back to any other matching monitor not taken yet.
+        let mut assigned: Vec<Option<&PhysicalMonitor>> = saved
+            .iter()
+            .map(|monitor| {
+                config
+                    .search_physical(&monitor.connector)
+                    .filter(|physical_monitor| monitor.id.matches(physical_monitor))
+            })
+            .collect();
+        for (index, monitor) in saved.iter().enumerate() {
+            if assigned[index].is_some() {
+                continue;
+            }
+            assigned[index] = config.monitors.iter().find(|physical_monitor| {
+                monitor.id.matches(physical_monitor)
+                    && !assigned
+                        .iter()
+                        .flatten()
+                        .any(|taken| taken.connector == physical_monitor.connector)
+            });
+        }
+
+        let mut assigned = saved.into_iter().zip(assigned);
+        self.logical_monitors
+            .iter()
+            .map(|logical_monitor| {
+                let monitors = (&mut assigned)
+                    .take(logical_monitor.monitors.len())
+                    .map(|(monitor, physical_monitor)| {
+                        let physical_monitor = physical_monitor
+                            .ok_or_else(|| Error::MissingMonitor(monitor.id.to_string()))?;
+                        let mode = physical_monitor
+                            .find_mode(
+                                monitor.mode.width,
+                                monitor.mode.height,
+                                Some(monitor.mode.refresh_rate),
+                            )
+                            .ok_or_else(|| {
+                                Error::MissingMode(monitor.id.to_string(), monitor.mode.to_string())
+                            })?;
+
+                        Ok(ApplyMonitor {
+                            connector: &physical_monitor.connector,
+                            mode_id: &mode.id,
+                        })
+                    })
+                    .collect::<Result<Vec<ApplyMonitor>, Error>>()?;
+
+                Ok(ApplyConfig {
+                    x_pos: logical_monitor.x,
+                    y_pos: logical_monitor.y,
+                    scale: logical_monitor.scale,
+                    transform: logical_monitor.transform,
+                    primary: logical_monitor.primary,
+                    monitors,
+                })
+            })
+            .collect()
+    }
+
+    /// The configs to apply, along with the layout mode to switch to if the profile needs a
+    /// different one.
+    fn prepare<'a>(
+        &self,
+        config: &'a DisplayConfig,
+    ) -> Result<(Vec<ApplyConfig<'a>>, Option<LayoutMode>), Error> {
+        let configs = self.to_apply_configs(config)?;
+
+        let layout_mode = if self.layout_mode == config.known_properties.layout_mode {
+            None
+        } else if config.known_properties.supports_changing_layout_mode {
+            Some(self.layout_mode)
+        } else {
+            return Err(Error::LayoutModeUnsupported);
+        };
+
+        Ok((configs, layout_mode))
+    }
+
+    /// The problems with applying the profile to the connected monitors, see
+    /// `DisplayConfig::validate_monitors_config`.
+    pub fn validate(&self, config: &DisplayConfig) -> Result<Vec<ValidationError>, Error> {
+        let (configs, layout_mode) = self.prepare(config)?;
+        Ok(config.validate_monitors_config(&configs, layout_mode))
+    }
+
+    /// Asks the display server whether it would accept the profile, without applying it.
+    pub fn verify(
+        &self,
+        config: &DisplayConfig,
+        proxy: &dbus::blocking::Proxy<&dbus::blocking::Connection>,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let (configs, layout_mode) = self.prepare(config)?;
+        config.verify_monitors_config(proxy, configs, layout_mode)?;
+        Ok(())
+    }
+
+    /// Applies the profile, switching the layout mode first if the profile needs a different one.
+    /// Unless `force` is set, a profile that looks invalid for the connected monitors is refused.
+    pub fn apply(
+        &self,
+        config: &DisplayConfig,
+        proxy: &dbus::blocking::Proxy<&dbus::blocking::Connection>,
+        persistent: bool,
+        force: bool,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let (configs, layout_mode) = self.prepare(config)?;
+
+        let errors = config.validate_monitors_config(&configs, layout_mode);
+        if !errors.is_empty() {
+            if !force {
+                return Err(Box::new(Error::Invalid(errors)));
+            }
+
+            println!("the profile looks invalid, applying it anyway:");
+            for error in errors.iter() {
+                println!("\t{}", error);
+            }
+        }
+
+        config.apply_monitors_config(proxy, configs, layout_mode, persistent)?;
+        Ok(())
+    }
+
+    /// The name of the saved profile matching the given configuration, if any.
+    pub fn find_current(
+        config: &DisplayConfig,
+    ) -> Result<Option<String>, Box<dyn std::error::Error>> {
+        let current = Profile::from(config);
+        Ok(Profile::list()?
+            .into_iter()
+            .find(|name| Profile::load(name).is_ok_and(|profile| profile.matches(&current))))
+    }
+}
+
+impl std::fmt::Display for Profile {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "layout-mode: {}", self.layout_mode)?;
+
+        for (i, logical_monitor) in self.logical_monitors.iter().enumerate() {
+            writeln!(f, "logical monitor {}:", i)?;
+            writeln!(
+                f,
+                "x: {}, y: {}, scale: {}, rotation: {}, primary: {}",
+                logical_monitor.x,
+                logical_monitor.y,
+                logical_monitor.scale,
+                Transform::from_bits_truncate(logical_monitor.transform),
+                if logical_monitor.primary { "yes" } else { "no" }
+            )?;
+            writeln!(f, "associated physical monitors:")?;
+            for monitor in logical_monitor.monitors.iter() {
+                writeln!(
+                    f,
+                    "\t{} ({}) {}",
+                    monitor.id, monitor.connector, monitor.mode
+                )?;
+            }
+        }
+
+        if !self.disabled.is_empty() {
+            writeln!(f, "disabled monitors:")?;
+            for monitor in self.disabled.iter() {
+                writeln!(f, "\t{}", monitor)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+pub fn handle(
+    opts: &CommandOptions,
+    config: &DisplayConfig,
+    proxy: &dbus::blocking::Proxy<&dbus::blocking::Connection>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    match opts {
+        CommandOptions::Save { name } => {
+            let path = Profile::from(config).save(name)?;
+            println!("saved profile '{}' to {}", name, path.display());
+        }
+        CommandOptions::List => {
+            let current = Profile::from(config);
+            for name in Profile::list()? {
+                let is_current =
+                    Profile::load(&name).is_ok_and(|profile| profile.matches(&current));
+                println!("{}{}", name, if is_current { " *" } else { "" });
+            }
+        }
+        CommandOptions::Show { name } => print!("{}", Profile::load(name)?),
+        CommandOptions::Apply {
+            name,
+            persistent,
+            dry_run,
+            force,
+        } => {
+            let profile = Profile::load(name)?;
+
+            println!("applying profile '{}'", name);
+            if *persistent {
+                println!("attempting to persist config to disk")
+            }
+
+            if !*dry_run {
+                return profile.apply(config, proxy, *persistent, *force);
+            }
+
+            // A dry run goes on to ask the display server, which has the final say.
+            let errors = profile.validate(config)?;
+            if !errors.is_empty() {
+                println!("the profile looks invalid:");
+                for error in errors.iter() {
+                    println!("\t{}", error);
+                }
+            }
+
+            match profile.verify(config, proxy) {
+                Ok(()) => println!("the display server accepts the profile."),
+                Err(error) => println!("the display server rejects the profile: {}", error),
+            }
+            println!("dry run: no changes made.");
+        }
+        CommandOptions::Delete { name } => {
+            std::fs::remove_file(profile_path(name)?)
+                .map_err(|_| Error::NotFound(name.to_string()))?;
+            println!("deleted profile '{}'", name);
+        }
+        CommandOptions::Current => match Profile::find_current(config)? {
+            Some(name) => println!("{}", name),
+            None => println!("no saved profile matches the current configuration."),
+        },
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gnome_randr::display_config::testing;
+
+    fn connectors<'a>(configs: &[ApplyConfig<'a>]) -> Vec<Vec<&'a str>> {
+        configs
+            .iter()
+            .map(|apply_config| {
+                apply_config
+                    .monitors
+                    .iter()
+                    .map(|monitor| monitor.connector)
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn pairs_up_identical_items_once_each() {
+        assert!(pair_up(&[1, 2, 2], &[2, 1, 2], |a, b| a == b));
+        assert!(!pair_up(&[1, 1, 2], &[1, 2, 2], |a, b| a == b));
+        assert!(!pair_up(&[1, 2], &[1, 2, 2], |a, b| a == b));
+    }
+
+    #[test]
+    fn keeps_identical_monitors_on_their_connectors() {
+        // Two of the same monitor, without a serial to tell them apart.
+        let mut config = testing::config(&[
+            (0, 0, 1.0, 0, true, &["DP-1"]),
+            (2560, 0, 1.0, 0, false, &["HDMI-1"]),
+        ]);
+        for monitor in config.monitors.iter_mut().skip(1) {
+            monitor.vendor = "DEL".to_string();
+            monitor.product = "DELL U2720Q".to_string();
+            monitor.serial = String::new();
+        }
+
+        let mut profile = Profile::from(&config);
+        profile.logical_monitors[1].monitors = vec![ProfileMonitor {
+            id: profile.logical_monitors[0].monitors[0].id.clone(),
+            connector: "HDMI-1".to_string(),
+            mode: ProfileMode {
+                width: 1920,
+                height: 1080,
+                refresh_rate: 60.0,
+            },
+        }];
+        // Listed first, HDMI-1 would take DP-1 if the saved connectors were ignored.
+        profile.logical_monitors.swap(0, 1);
+
+        let configs = profile.to_apply_configs(&config).unwrap();
+        assert_eq!(connectors(&configs), vec![vec!["HDMI-1"], vec!["DP-1"]]);
+        assert_eq!(configs[0].monitors[0].mode_id, "1920x1080@60.000");
+        assert_eq!(configs[1].monitors[0].mode_id, "2560x1440@59.951");
+    }
+
+    #[test]
+    fn finds_monitors_moved_to_another_connector() {
+        let config = testing::laptop_and_monitor();
+        let mut profile = Profile::from(&config);
+        profile.logical_monitors[1].monitors[0].connector = "DP-2".to_string();
+
+        let configs = profile.to_apply_configs(&config).unwrap();
+        assert_eq!(connectors(&configs), vec![vec!["eDP-1"], vec!["DP-1"]]);
+        assert_eq!(configs[1].monitors[0].mode_id, "2560x1440@59.951");
+    }
+
+    #[test]
+    fn fails_on_a_missing_monitor() {
+        let config = testing::laptop_and_monitor();
+        let mut profile = Profile::from(&config);
+        profile.logical_monitors[1].monitors[0].id.serial = "XYZ789".to_string();
+
+        match profile.to_apply_configs(&config) {
+            Err(Error::MissingMonitor(monitor)) => assert_eq!(monitor, "DEL DELL U2720Q XYZ789"),
+            _ => panic!("expected a missing monitor"),
+        }
+    }
+
+    #[test]
+    fn reads_the_layout_mode_by_name() {
+        let profile: Profile =
+            serde_json::from_str(r#"{"layout-mode": "physical", "logical-monitors": []}"#).unwrap();
+        assert_eq!(profile.layout_mode, LayoutMode::Physical);
+
+        let error = serde_json::from_str::<Profile>(
+            r#"{"layout-mode": "physicla", "logical-monitors": []}"#,
+        )
+        .err()
+        .unwrap();
+        assert!(error.to_string().contains("physicla"));
+    }
+}
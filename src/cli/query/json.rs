@@ -0,0 +1,276 @@
+use dbus::arg::{ArgType, PropMap, RefArg};
+use gnome_randr::{
+    display_config::{logical_monitor, physical_monitor, KnownProperties},
+    DisplayConfig,
+};
+use serde::Serialize;
+use serde_json::{Map, Number, Value};
+
+/// Bumped whenever a field is removed or changes meaning. New fields may be added without bumping
+/// it, so consumers should ignore fields they don't know.
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// Converts a D-Bus value to JSON. Dicts become objects (with non-string keys stringified), while
+/// arrays and structs become arrays.
+pub fn to_json(value: &dyn RefArg) -> Value {
+    match value.arg_type() {
+        ArgType::Boolean => Value::Bool(value.as_u64() == Some(1)),
+        ArgType::Byte | ArgType::UInt16 | ArgType::UInt32 | ArgType::UInt64 | ArgType::UnixFd => {
+            value.as_u64().map_or(Value::Null, Value::from)
+        }
+        ArgType::Int16 | ArgType::Int32 | ArgType::Int64 => {
+            value.as_i64().map_or(Value::Null, Value::from)
+        }
+        ArgType::Double => value
+            .as_f64()
+            .and_then(Number::from_f64)
+            .map_or(Value::Null, Value::Number),
+        ArgType::String | ArgType::ObjectPath | ArgType::Signature => {
+            value.as_str().map_or(Value::Null, Value::from)
+        }
+        ArgType::Variant => value
+            .as_iter()
+            .and_then(|mut inner| inner.next().map(to_json))
+            .unwrap_or(Value::Null),
+        ArgType::Array if value.signature().starts_with("a{") => {
+            let mut object = Map::new();
+            if let Some(mut entries) = value.as_iter() {
+                while let (Some(key), Some(entry)) = (entries.next(), entries.next()) {
+                    let key = match to_json(key) {
+                        Value::String(key) => key,
+                        key => key.to_string(),
+                    };
+                    object.insert(key, to_json(entry));
+                }
+            }
+            Value::Object(object)
+        }
+        ArgType::Array | ArgType::Struct | ArgType::DictEntry => Value::Array(
+            value
+                .as_iter()
+                .map(|items| items.map(to_json).collect())
+                .unwrap_or_default(),
+        ),
+        ArgType::Invalid => Value::Null,
+    }
+}
+
+fn properties(properties: &PropMap) -> Map<String, Value> {
+    properties
+        .iter()
+        .map(|(key, value)| (key.clone(), to_json(&value.0)))
+        .collect()
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "kebab-case")]
+struct JsonKnownProperties {
+    supports_mirroring: bool,
+    layout_mode: String,
+    supports_changing_layout_mode: bool,
+    global_scale_required: bool,
+}
+
+impl JsonKnownProperties {
+    fn from(known_properties: &KnownProperties) -> JsonKnownProperties {
+        JsonKnownProperties {
+            supports_mirroring: known_properties.supports_mirroring,
+            layout_mode: known_properties.layout_mode.to_string(),
+            supports_changing_layout_mode: known_properties.supports_changing_layout_mode,
+            global_scale_required: known_properties.global_scale_required,
+        }
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "kebab-case")]
+struct JsonMonitor<'a> {
+    connector: &'a str,
+    vendor: &'a str,
+    product: &'a str,
+    serial: &'a str,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "kebab-case")]
+struct JsonLogicalMonitor<'a> {
+    x: i32,
+    y: i32,
+    scale: f64,
+    transform: u32,
+    rotation: String,
+    flipped: bool,
+    primary: bool,
+    monitors: Vec<JsonMonitor<'a>>,
+    properties: Map<String, Value>,
+}
+
+impl JsonLogicalMonitor<'_> {
+    fn from(logical_monitor: &logical_monitor::LogicalMonitor) -> JsonLogicalMonitor<'_> {
+        let transform = logical_monitor.transform;
+        JsonLogicalMonitor {
+            x: logical_monitor.x,
+            y: logical_monitor.y,
+            scale: logical_monitor.scale,
+            transform: transform.bits(),
+            rotation: (transform - logical_monitor::Transform::FLIPPED).to_string(),
+            flipped: transform.contains(logical_monitor::Transform::FLIPPED),
+            primary: logical_monitor.primary,
+            monitors: logical_monitor
+                .monitors
+                .iter()
+                .map(|monitor| JsonMonitor {
+                    connector: &monitor.connector,
+                    vendor: &monitor.vendor,
+                    product: &monitor.product,
+                    serial: &monitor.serial,
+                })
+                .collect(),
+            properties: properties(&logical_monitor.properties),
+        }
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "kebab-case")]
+struct JsonMode<'a> {
+    id: &'a str,
+    width: i32,
+    height: i32,
+    refresh_rate: f64,
+    preferred_scale: f64,
+    supported_scales: &'a [f64],
+    is_current: bool,
+    is_preferred: bool,
+    properties: Map<String, Value>,
+}
+
+impl JsonMode<'_> {
+    fn from(mode: &physical_monitor::Mode) -> JsonMode<'_> {
+        JsonMode {
+            id: &mode.id,
+            width: mode.width,
+            height: mode.height,
+            refresh_rate: mode.refresh_rate,
+            preferred_scale: mode.preferred_scale,
+            supported_scales: &mode.supported_scales,
+            is_current: mode.known_properties.is_current,
+            is_preferred: mode.known_properties.is_preferred,
+            properties: properties(&mode.properties),
+        }
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "kebab-case")]
+struct JsonPrivacyScreen {
+    enabled: bool,
+    locked: bool,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "kebab-case")]
+struct JsonKnownMonitorProperties<'a> {
+    width_mm: Option<i32>,
+    height_mm: Option<i32>,
+    is_underscanning: Option<bool>,
+    max_screen_size: Option<(i32, i32)>,
+    is_builtin: bool,
+    display_name: Option<&'a str>,
+    min_refresh_rate: Option<i32>,
+    privacy_screen_state: Option<JsonPrivacyScreen>,
+    is_for_lease: bool,
+}
+
+impl JsonKnownMonitorProperties<'_> {
+    fn from(
+        known_properties: &physical_monitor::KnownMonitorProperties,
+    ) -> JsonKnownMonitorProperties<'_> {
+        JsonKnownMonitorProperties {
+            width_mm: known_properties.width_mm,
+            height_mm: known_properties.height_mm,
+            is_underscanning: known_properties.is_underscanning,
+            max_screen_size: known_properties.max_screen_size,
+            is_builtin: known_properties.is_builtin,
+            display_name: known_properties.display_name.as_deref(),
+            min_refresh_rate: known_properties.min_refresh_rate,
+            privacy_screen_state: known_properties.privacy_screen.map(|privacy_screen| {
+                JsonPrivacyScreen {
+                    enabled: privacy_screen.enabled,
+                    locked: privacy_screen.locked,
+                }
+            }),
+            is_for_lease: known_properties.is_for_lease,
+        }
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "kebab-case")]
+struct JsonPhysicalMonitor<'a> {
+    connector: &'a str,
+    vendor: &'a str,
+    product: &'a str,
+    serial: &'a str,
+    enabled: bool,
+    modes: Vec<JsonMode<'a>>,
+    known_properties: JsonKnownMonitorProperties<'a>,
+    properties: Map<String, Value>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "kebab-case")]
+struct JsonDisplayConfig<'a> {
+    version: u32,
+    serial: u32,
+    known_properties: JsonKnownProperties,
+    properties: Map<String, Value>,
+    logical_monitors: Vec<JsonLogicalMonitor<'a>>,
+    monitors: Vec<JsonPhysicalMonitor<'a>>,
+}
+
+/// Renders the config as JSON. With a connector, only the monitor on that connector and the
+/// logical monitor it belongs to are included.
+pub fn format(
+    config: &DisplayConfig,
+    connector: Option<&str>,
+) -> Result<String, serde_json::Error> {
+    let included = |candidate: &str| connector.is_none_or(|connector| connector == candidate);
+
+    let json = JsonDisplayConfig {
+        version: SCHEMA_VERSION,
+        serial: config.serial,
+        known_properties: JsonKnownProperties::from(&config.known_properties),
+        properties: properties(&config.properties),
+        logical_monitors: config
+            .logical_monitors
+            .iter()
+            .filter(|logical_monitor| {
+                logical_monitor
+                    .monitors
+                    .iter()
+                    .any(|monitor| included(&monitor.connector))
+            })
+            .map(JsonLogicalMonitor::from)
+            .collect(),
+        monitors: config
+            .monitors
+            .iter()
+            .filter(|monitor| included(&monitor.connector))
+            .map(|monitor| JsonPhysicalMonitor {
+                connector: &monitor.connector,
+                vendor: &monitor.vendor,
+                product: &monitor.product,
+                serial: &monitor.serial,
+                enabled: config.search_logical(&monitor.connector).is_some(),
+                modes: monitor.modes.iter().map(JsonMode::from).collect(),
+                known_properties: JsonKnownMonitorProperties::from(&monitor.known_properties),
+                properties: properties(&monitor.properties),
+            })
+            .collect(),
+    };
+
+    let mut output = serde_json::to_string_pretty(&json)?;
+    output.push('\n');
+    Ok(output)
+}
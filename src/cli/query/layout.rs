@@ -0,0 +1,197 @@
+use std::fmt::Write;
+
+use gnome_randr::{display_config::geometry::Rect, DisplayConfig};
+
+const DEFAULT_WIDTH: usize = 80;
+// Terminal cells are about twice as tall as they are wide.
+const CELL_ASPECT: f64 = 2.0;
+// Enough rows inside the smallest box for every label line.
+const MIN_INNER_HEIGHT: f64 = 4.0;
+
+/// The width of the terminal stdout is attached to, falling back to $COLUMNS and then 80 columns.
+fn terminal_width() -> usize {
+    let mut size: libc::winsize = unsafe { std::mem::zeroed() };
+    if unsafe { libc::ioctl(libc::STDOUT_FILENO, libc::TIOCGWINSZ, &mut size) } == 0
+        && size.ws_col > 0
+    {
+        return size.ws_col as usize;
+    }
+
+    std::env::var("COLUMNS")
+        .ok()
+        .and_then(|columns| columns.parse().ok())
+        .filter(|columns| *columns > 0)
+        .unwrap_or(DEFAULT_WIDTH)
+}
+
+/// A box on the canvas, in cells. Neighbouring boxes share their border.
+struct CellRect {
+    left: usize,
+    top: usize,
+    right: usize,
+    bottom: usize,
+}
+
+struct Canvas {
+    cells: Vec<Vec<char>>,
+}
+
+impl Canvas {
+    fn new(width: usize, height: usize) -> Canvas {
+        Canvas {
+            cells: vec![vec![' '; width]; height],
+        }
+    }
+
+    fn put(&mut self, x: usize, y: usize, c: char) {
+        if let Some(cell) = self.cells.get_mut(y).and_then(|row| row.get_mut(x)) {
+            // Corners win over edges where borders meet.
+            if *cell != '+' {
+                *cell = c;
+            }
+        }
+    }
+
+    fn draw_box(&mut self, rect: &CellRect) {
+        for x in rect.left + 1..rect.right {
+            self.put(x, rect.top, '-');
+            self.put(x, rect.bottom, '-');
+        }
+        for y in rect.top + 1..rect.bottom {
+            self.put(rect.left, y, '|');
+            self.put(rect.right, y, '|');
+        }
+        for (x, y) in [
+            (rect.left, rect.top),
+            (rect.right, rect.top),
+            (rect.left, rect.bottom),
+            (rect.right, rect.bottom),
+        ] {
+            if let Some(cell) = self.cells.get_mut(y).and_then(|row| row.get_mut(x)) {
+                *cell = '+';
+            }
+        }
+    }
+
+    /// Writes the lines inside the box, cutting off whatever doesn't fit.
+    fn label(&mut self, rect: &CellRect, lines: &[String]) {
+        let width = rect.right.saturating_sub(rect.left + 3);
+        for (i, line) in lines
+            .iter()
+            .enumerate()
+            .take(rect.bottom.saturating_sub(rect.top + 1))
+        {
+            for (j, c) in line.chars().take(width).enumerate() {
+                self.cells[rect.top + 1 + i][rect.left + 2 + j] = c;
+            }
+        }
+    }
+}
+
+impl std::fmt::Display for Canvas {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for row in self.cells.iter() {
+            writeln!(f, "{}", row.iter().collect::<String>().trim_end())?;
+        }
+        Ok(())
+    }
+}
+
+/// Draws the logical monitors as boxes at their place in the layout, scaled to fit in `width`
+/// columns.
+pub fn render(config: &DisplayConfig, width: usize) -> Result<String, std::fmt::Error> {
+    let mut output = String::new();
+
+    let placed: Vec<(&_, Rect)> = config
+        .logical_monitors
+        .iter()
+        .filter_map(|logical_monitor| Some((logical_monitor, logical_monitor.rect(config)?)))
+        .collect();
+    let bounds = match config.bounding_box() {
+        Some(bounds) if bounds.width > 0 && bounds.height > 0 => bounds,
+        _ => {
+            writeln!(output, "no enabled monitors.")?;
+            return Ok(output);
+        }
+    };
+
+    let scale_x = (width.max(2) - 1) as f64 / bounds.width as f64;
+    let min_height = placed
+        .iter()
+        .map(|(_, rect)| rect.height)
+        .min()
+        .unwrap_or(bounds.height)
+        .max(1);
+    let scale_y = (scale_x / CELL_ASPECT).max((MIN_INNER_HEIGHT + 1.0) / min_height as f64);
+
+    let cell_x = |x: i32| ((x - bounds.x) as f64 * scale_x).round() as usize;
+    let cell_y = |y: i32| ((y - bounds.y) as f64 * scale_y).round() as usize;
+
+    let mut canvas = Canvas::new(cell_x(bounds.right()) + 1, cell_y(bounds.bottom()) + 1);
+    let boxes: Vec<CellRect> = placed
+        .iter()
+        .map(|(_, rect)| CellRect {
+            left: cell_x(rect.x),
+            top: cell_y(rect.y),
+            right: cell_x(rect.right()),
+            bottom: cell_y(rect.bottom()),
+        })
+        .collect();
+
+    for cell_rect in boxes.iter() {
+        canvas.draw_box(cell_rect);
+    }
+
+    for ((logical_monitor, _), cell_rect) in placed.iter().zip(boxes.iter()) {
+        let connectors = logical_monitor
+            .monitors
+            .iter()
+            .map(|monitor| monitor.connector.as_str())
+            .collect::<Vec<_>>()
+            .join(" + ");
+
+        let mut lines = vec![if logical_monitor.primary {
+            format!("{} *", connectors)
+        } else {
+            connectors
+        }];
+        if let Some(mode) = logical_monitor
+            .monitors
+            .first()
+            .and_then(|monitor| config.search_physical(&monitor.connector))
+            .and_then(|physical_monitor| physical_monitor.current_mode())
+        {
+            lines.push(format!(
+                "{}x{}@{:.2}",
+                mode.width, mode.height, mode.refresh_rate
+            ));
+        }
+        lines.push(format!("scale {}", logical_monitor.scale));
+        lines.push(logical_monitor.transform.to_string());
+
+        canvas.label(cell_rect, &lines);
+    }
+
+    write!(output, "{}", canvas)?;
+    writeln!(
+        output,
+        "{} ({} layout mode), * primary",
+        bounds, config.known_properties.layout_mode
+    )?;
+
+    let disabled: Vec<&str> = config
+        .monitors
+        .iter()
+        .filter(|physical_monitor| config.search_logical(&physical_monitor.connector).is_none())
+        .map(|physical_monitor| physical_monitor.connector.as_str())
+        .collect();
+    if !disabled.is_empty() {
+        writeln!(output, "disabled: {}", disabled.join(", "))?;
+    }
+
+    Ok(output)
+}
+
+pub fn format(config: &DisplayConfig) -> Result<String, std::fmt::Error> {
+    render(config, terminal_width())
+}
@@ -0,0 +1,173 @@
+use std::path::PathBuf;
+
+use gnome_randr::{display_config::selector, DisplayConfig};
+use structopt::StructOpt;
+
+mod json;
+mod layout;
+mod physical;
+mod svg;
+mod template;
+mod xrandr;
+
+#[derive(StructOpt, Default)]
+pub struct CommandOptions {
+    #[structopt(
+        help = "the connector used for the physical monitor, or a selector.",
+        long_help = "query by the connector used for the physical monitor, e.g. \"HDMI-1\". You can find these with \"query\" (no arguments) if you're unsure.\n\nSelectors pick the monitor by something other than its connector, which changes between dock ports: 'vendor:DEL', 'serial:ABC123', 'name:\"Dell U2720Q\"' (the display or product name), 'builtin', 'external', 'primary' or 'logical:N' (the Nth logical monitor listed by \"query\", counting from 0). Connectors and selector values may be globs such as 'DP-*'. The selector must match exactly one monitor."
+    )]
+    pub connector: Option<String>,
+
+    #[structopt(short, long)]
+    pub summary: bool,
+
+    #[structopt(
+        long,
+        conflicts_with = "summary",
+        help = "Print the configuration as JSON",
+        long_help = "Print the whole configuration as JSON, including properties the display server reports that gnome-randr doesn't know about. The output carries a \"version\" field, which is bumped whenever a field is removed or changes meaning."
+    )]
+    pub json: bool,
+
+    #[structopt(
+        long,
+        conflicts_with_all = &["summary", "json"],
+        help = "Print the configuration the way `xrandr --query` does",
+        long_help = "Print the configuration the way `xrandr --query` does, for tools that parse its output. Sizes are the ones the monitors take up in the layout, so they are scaled in the logical layout mode."
+    )]
+    pub xrandr: bool,
+
+    #[structopt(
+        long,
+        value_name = "template",
+        conflicts_with_all = &["summary", "json", "xrandr"],
+        help = "Print each monitor using a template, or `shell` for shell assignments",
+        long_help = "Print each monitor on its own line using a template, e.g. '{connector}: {mode.width}x{mode.height}'. The placeholders are {connector}, {vendor}, {product}, {serial}, {enabled}, {x}, {y}, {scale}, {rotation}, {primary}, {mode.width}, {mode.height} and {mode.rate}; use {{ and }} for literal braces. Values that need an enabled monitor are empty for disabled ones.\n\n`--format shell` instead prints assignments that are safe to `eval`, e.g. DP_1_MODE_WIDTH='2560', along with CONNECTORS listing every connector."
+    )]
+    pub format: Option<template::Format>,
+
+    #[structopt(
+        long,
+        conflicts_with_all = &["summary", "json", "xrandr", "format"],
+        help = "Print the physical size, DPI and aspect ratio of each monitor",
+        long_help = "Print the physical size, DPI and aspect ratio of each monitor. Monitors that don't report their size, or report one that can't be real (many projectors report their aspect ratio instead), are marked as unknown rather than given made-up numbers."
+    )]
+    pub physical: bool,
+
+    #[structopt(
+        long,
+        conflicts_with_all = &["connector", "summary", "json", "xrandr", "format", "physical"],
+        help = "Draw the layout of the logical monitors",
+        long_help = "Draw the layout of the logical monitors as boxes, scaled to fit the width of the terminal. Each box lists its connectors (several when mirrored), mode, scale and rotation, and the primary monitor is marked with '*'. Disabled monitors are listed below the drawing."
+    )]
+    pub layout: bool,
+
+    #[structopt(
+        long,
+        value_name = "file",
+        parse(from_os_str),
+        conflicts_with_all = &["connector", "summary", "json", "xrandr", "format", "physical", "layout"],
+        help = "Write a diagram of the layout to an SVG file",
+        long_help = "Write a diagram of the layout to an SVG file, or to stdout with '-'. Logical monitors are drawn at their coordinates in the layout, labelled with their connectors, names, mode, scale and rotation, with an arrow pointing at the top of the picture. Disabled monitors are listed in a legend."
+    )]
+    pub svg: Option<PathBuf>,
+}
+
+#[derive(Debug)]
+pub enum Error {
+    NotFound,
+    Selector(selector::Error),
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self {
+            Error::NotFound => write!(f, "fatal: unable to find output."),
+            Error::Selector(error) => write!(f, "fatal: {}", error),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+pub fn handle(
+    opts: &CommandOptions,
+    config: &DisplayConfig,
+) -> Result<String, Box<dyn std::error::Error>> {
+    // Selectors such as `vendor:DEL` are resolved to the connector they match.
+    let connector = match &opts.connector {
+        Some(selector) => Some(
+            config
+                .select(selector)
+                .map_err(Error::Selector)?
+                .connector
+                .as_str(),
+        ),
+        None => None,
+    };
+
+    if opts.json {
+        return Ok(json::format(config, connector)?);
+    }
+
+    if let Some(format) = &opts.format {
+        return Ok(format.format(
+            config,
+            config.monitors.iter().filter(|physical_monitor| {
+                connector.is_none_or(|connector| physical_monitor.connector == connector)
+            }),
+        ));
+    }
+
+    if opts.physical {
+        return Ok(physical::format(config, connector)?);
+    }
+
+    if let Some(path) = &opts.svg {
+        let svg = svg::render(config)?;
+        if path.as_os_str() == "-" {
+            return Ok(svg);
+        }
+
+        std::fs::write(path, svg)?;
+        return Ok(format!("wrote the layout to {}\n", path.display()));
+    }
+
+    if opts.layout {
+        return Ok(layout::format(config)?);
+    }
+
+    if opts.xrandr {
+        return Ok(xrandr::format(config, connector)?);
+    }
+
+    Ok(match connector {
+        Some(connector) => match config.search(connector) {
+            Some((logical_monitor, physical_monitor)) => {
+                if opts.summary {
+                    format!("{}", logical_monitor)
+                } else {
+                    format!("{}\n{}", logical_monitor, physical_monitor)
+                }
+            }
+            // A disabled monitor isn't part of any logical monitor, but its modes are still useful.
+            None => {
+                let physical_monitor = config.search_physical(connector).ok_or(Error::NotFound)?;
+                if opts.summary {
+                    format!("{} is disabled.\n", physical_monitor.connector)
+                } else {
+                    format!("{}", physical_monitor)
+                }
+            }
+        },
+        None => {
+            if opts.summary {
+                let mut s = String::new();
+                config.format(&mut s, true)?;
+                s.to_string()
+            } else {
+                format!("{}", config)
+            }
+        }
+    })
+}
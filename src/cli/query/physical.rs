@@ -0,0 +1,76 @@
+use std::fmt::Write;
+
+use gnome_randr::{
+    display_config::physical_monitor::{DensityClass, PhysicalMonitor},
+    DisplayConfig,
+};
+
+fn format_monitor(
+    writer: &mut dyn Write,
+    config: &DisplayConfig,
+    physical_monitor: &PhysicalMonitor,
+) -> std::fmt::Result {
+    let known_properties = &physical_monitor.known_properties;
+    match &known_properties.display_name {
+        Some(display_name) => {
+            writeln!(writer, "{} ({}):", physical_monitor.connector, display_name)?
+        }
+        None => writeln!(writer, "{}:", physical_monitor.connector)?,
+    }
+
+    write!(writer, "\tsize: {}", known_properties.physical_size())?;
+    match known_properties.diagonal_inches() {
+        Some(diagonal) => writeln!(writer, ", {:.1}in diagonal", diagonal)?,
+        None => writeln!(writer)?,
+    }
+
+    // Disabled monitors are described by the mode they would be enabled with.
+    let logical_monitor = config.search_logical(&physical_monitor.connector);
+    let mode = match logical_monitor {
+        Some(_) => physical_monitor.current_mode(),
+        None => physical_monitor.preferred_mode(),
+    };
+    let mode = match mode {
+        Some(mode) => mode,
+        None => return Ok(()),
+    };
+
+    writeln!(
+        writer,
+        "\tmode: {}x{}{}",
+        mode.width,
+        mode.height,
+        match logical_monitor {
+            Some(logical_monitor) => format!(", scale {}", logical_monitor.scale),
+            None => " (preferred, disabled)".to_string(),
+        }
+    )?;
+    writeln!(writer, "\taspect ratio: {}", mode.aspect_ratio())?;
+
+    match physical_monitor.dpi(mode) {
+        Some(dpi) => {
+            write!(writer, "\tdpi: {:.0} physical", dpi)?;
+            if let Some(logical_monitor) = logical_monitor {
+                write!(writer, ", {:.0} effective", dpi / logical_monitor.scale)?;
+            }
+            writeln!(writer)?;
+            writeln!(writer, "\tdensity: {}", DensityClass::from(dpi))?;
+        }
+        None => writeln!(writer, "\tdpi: unknown")?,
+    }
+
+    Ok(())
+}
+
+/// Describes the physical size and pixel density of each monitor.
+pub fn format(config: &DisplayConfig, connector: Option<&str>) -> Result<String, std::fmt::Error> {
+    let mut output = String::new();
+
+    for physical_monitor in config.monitors.iter().filter(|physical_monitor| {
+        connector.is_none_or(|connector| physical_monitor.connector == connector)
+    }) {
+        format_monitor(&mut output, config, physical_monitor)?;
+    }
+
+    Ok(output)
+}
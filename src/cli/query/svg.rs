@@ -0,0 +1,219 @@
+use std::fmt::Write;
+
+use gnome_randr::{
+    display_config::{logical_monitor::Transform, physical_monitor::PhysicalMonitor},
+    DisplayConfig,
+};
+
+const MONITOR_FILL: &str = "#dce6f2";
+const PRIMARY_FILL: &str = "#c7dcf5";
+const STROKE: &str = "#2e3f52";
+const BADGE_FILL: &str = "#2e6fbf";
+const TEXT: &str = "#1a1a1a";
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// The name the monitor goes by, e.g. "DELL U2720Q", falling back to its vendor and product.
+fn display_name(physical_monitor: &PhysicalMonitor) -> String {
+    physical_monitor
+        .known_properties
+        .display_name
+        .clone()
+        .unwrap_or_else(|| format!("{} {}", physical_monitor.vendor, physical_monitor.product))
+}
+
+/// The angle to turn an upwards arrow by so it points at the top of the rotated picture.
+fn rotation_angle(transform: Transform) -> u32 {
+    (transform & Transform::R270).bits() * 90
+}
+
+/// Renders the logical monitors at their real coordinates in the layout, with disabled monitors
+/// listed in a legend to the right.
+pub fn render(config: &DisplayConfig) -> Result<String, std::fmt::Error> {
+    let bounds = config.bounding_box();
+    let (layout_width, layout_height) =
+        bounds.map_or((0, 0), |bounds| (bounds.width, bounds.height));
+
+    // Size everything else relative to the layout, so the diagram reads the same at any size.
+    let unit = (layout_width.max(layout_height) as f64 / 100.0).max(8.0);
+    let font_size = unit * 2.0;
+    let line_height = font_size * 1.3;
+    let margin = unit * 4.0;
+
+    let disabled: Vec<&PhysicalMonitor> = config
+        .monitors
+        .iter()
+        .filter(|physical_monitor| config.search_logical(&physical_monitor.connector).is_none())
+        .collect();
+    let legend_width = if disabled.is_empty() {
+        0.0
+    } else {
+        disabled
+            .iter()
+            .map(|physical_monitor| {
+                (physical_monitor.connector.len() + display_name(physical_monitor).len() + 3) as f64
+            })
+            .fold(12.0, f64::max)
+            * font_size
+            * 0.6
+            + margin
+    };
+
+    let width = layout_width as f64 + legend_width + margin * 2.0;
+    let height =
+        (layout_height as f64).max(line_height * (disabled.len() + 1) as f64) + margin * 2.0;
+    let (offset_x, offset_y) = bounds.map_or((0, 0), |bounds| (bounds.x, bounds.y));
+    let x = |value: i32| (value - offset_x) as f64 + margin;
+    let y = |value: i32| (value - offset_y) as f64 + margin;
+
+    let mut output = String::new();
+    writeln!(
+        output,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{w:.0}" height="{h:.0}" viewBox="0 0 {w:.0} {h:.0}" font-family="sans-serif" font-size="{:.0}">"#,
+        font_size,
+        w = width,
+        h = height
+    )?;
+    writeln!(
+        output,
+        r#"  <rect width="100%" height="100%" fill="white"/>"#
+    )?;
+
+    for logical_monitor in config.logical_monitors.iter() {
+        let rect = match logical_monitor.rect(config) {
+            Some(rect) => rect,
+            None => continue,
+        };
+        let physical_monitors: Vec<&PhysicalMonitor> = logical_monitor
+            .monitors
+            .iter()
+            .filter_map(|monitor| config.search_physical(&monitor.connector))
+            .collect();
+
+        writeln!(output, "  <g>")?;
+        writeln!(
+            output,
+            r#"    <rect x="{:.0}" y="{:.0}" width="{}" height="{}" fill="{}" stroke="{}" stroke-width="{:.0}"/>"#,
+            x(rect.x),
+            y(rect.y),
+            rect.width,
+            rect.height,
+            if logical_monitor.primary {
+                PRIMARY_FILL
+            } else {
+                MONITOR_FILL
+            },
+            STROKE,
+            unit / 2.0
+        )?;
+
+        let mut lines = vec![(
+            physical_monitors
+                .iter()
+                .map(|physical_monitor| physical_monitor.connector.as_str())
+                .collect::<Vec<_>>()
+                .join(" + "),
+            true,
+        )];
+        for physical_monitor in physical_monitors.iter() {
+            lines.push((display_name(physical_monitor), false));
+        }
+        if let Some(mode) = physical_monitors
+            .first()
+            .and_then(|physical_monitor| physical_monitor.current_mode())
+        {
+            lines.push((
+                format!(
+                    "{}x{} @ {:.2} Hz",
+                    mode.width, mode.height, mode.refresh_rate
+                ),
+                false,
+            ));
+        }
+        lines.push((format!("scale {}", logical_monitor.scale), false));
+        lines.push((format!("rotation {}", logical_monitor.transform), false));
+
+        for (i, (line, bold)) in lines.iter().enumerate() {
+            writeln!(
+                output,
+                r#"    <text x="{:.0}" y="{:.0}" fill="{}"{}>{}</text>"#,
+                x(rect.x) + unit * 2.0,
+                y(rect.y) + unit * 2.0 + line_height * (i + 1) as f64,
+                TEXT,
+                if *bold { r#" font-weight="bold""# } else { "" },
+                escape(line)
+            )?;
+        }
+
+        // An arrow in the bottom right corner points at the top of the picture.
+        let (arrow_x, arrow_y) = (x(rect.right()) - unit * 6.0, y(rect.bottom()) - unit * 6.0);
+        writeln!(
+            output,
+            r#"    <path d="M {cx:.0} {top:.0} L {right:.0} {mid:.0} L {shaft_right:.0} {mid:.0} L {shaft_right:.0} {bottom:.0} L {shaft_left:.0} {bottom:.0} L {shaft_left:.0} {mid:.0} L {left:.0} {mid:.0} Z" fill="{}" transform="rotate({} {cx:.0} {cy:.0})"/>"#,
+            STROKE,
+            rotation_angle(logical_monitor.transform),
+            cx = arrow_x,
+            cy = arrow_y,
+            top = arrow_y - unit * 3.0,
+            mid = arrow_y,
+            bottom = arrow_y + unit * 3.0,
+            left = arrow_x - unit * 2.0,
+            right = arrow_x + unit * 2.0,
+            shaft_left = arrow_x - unit * 0.75,
+            shaft_right = arrow_x + unit * 0.75,
+        )?;
+
+        if logical_monitor.primary {
+            let badge_width = font_size * 5.0;
+            let badge_x = x(rect.right()) - badge_width - unit * 2.0;
+            let badge_y = y(rect.y) + unit * 2.0;
+            writeln!(
+                output,
+                r#"    <rect x="{:.0}" y="{:.0}" width="{:.0}" height="{:.0}" rx="{:.0}" fill="{}"/>"#,
+                badge_x, badge_y, badge_width, line_height, unit, BADGE_FILL
+            )?;
+            writeln!(
+                output,
+                r#"    <text x="{:.0}" y="{:.0}" fill="white" font-weight="bold" text-anchor="middle">PRIMARY</text>"#,
+                badge_x + badge_width / 2.0,
+                badge_y + font_size
+            )?;
+        }
+        writeln!(output, "  </g>")?;
+    }
+
+    if !disabled.is_empty() {
+        let legend_x = layout_width as f64 + margin * 2.0;
+        writeln!(output, "  <g>")?;
+        writeln!(
+            output,
+            r#"    <text x="{:.0}" y="{:.0}" fill="{}" font-weight="bold">Disabled</text>"#,
+            legend_x,
+            margin + font_size,
+            TEXT
+        )?;
+        for (i, physical_monitor) in disabled.iter().enumerate() {
+            writeln!(
+                output,
+                r#"    <text x="{:.0}" y="{:.0}" fill="{}">{}</text>"#,
+                legend_x,
+                margin + font_size + line_height * (i + 1) as f64,
+                TEXT,
+                escape(&format!(
+                    "{} ({})",
+                    physical_monitor.connector,
+                    display_name(physical_monitor)
+                ))
+            )?;
+        }
+        writeln!(output, "  </g>")?;
+    }
+
+    writeln!(output, "</svg>")?;
+    Ok(output)
+}
@@ -0,0 +1,357 @@
+use gnome_randr::{display_config::physical_monitor::PhysicalMonitor, DisplayConfig};
+
+#[derive(Debug)]
+pub enum Error {
+    UnknownPlaceholder(String),
+    Unterminated,
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self {
+            Error::UnknownPlaceholder(name) => write!(
+                f,
+                "unknown placeholder '{{{}}}', valid placeholders are: {}",
+                name,
+                Placeholder::ALL
+                    .iter()
+                    .map(|placeholder| format!("{{{}}}", placeholder))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            Error::Unterminated => write!(
+                f,
+                "unterminated placeholder, use '{{{{' and '}}}}' for literal braces"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Placeholder {
+    Connector,
+    Vendor,
+    Product,
+    Serial,
+    Enabled,
+    X,
+    Y,
+    Scale,
+    Rotation,
+    Primary,
+    ModeWidth,
+    ModeHeight,
+    ModeRate,
+}
+
+impl Placeholder {
+    const ALL: [Placeholder; 13] = [
+        Placeholder::Connector,
+        Placeholder::Vendor,
+        Placeholder::Product,
+        Placeholder::Serial,
+        Placeholder::Enabled,
+        Placeholder::X,
+        Placeholder::Y,
+        Placeholder::Scale,
+        Placeholder::Rotation,
+        Placeholder::Primary,
+        Placeholder::ModeWidth,
+        Placeholder::ModeHeight,
+        Placeholder::ModeRate,
+    ];
+
+    /// The value for the given monitor. Values that only apply to enabled monitors are empty for
+    /// disabled ones.
+    fn value(&self, config: &DisplayConfig, physical_monitor: &PhysicalMonitor) -> String {
+        let logical_monitor = config.search_logical(&physical_monitor.connector);
+        let mode = physical_monitor.current_mode();
+
+        let yes_no = |val: bool| if val { "yes" } else { "no" }.to_string();
+
+        match self {
+            Placeholder::Connector => physical_monitor.connector.clone(),
+            Placeholder::Vendor => physical_monitor.vendor.clone(),
+            Placeholder::Product => physical_monitor.product.clone(),
+            Placeholder::Serial => physical_monitor.serial.clone(),
+            Placeholder::Enabled => yes_no(logical_monitor.is_some()),
+            Placeholder::X => logical_monitor.map_or(String::new(), |lm| lm.x.to_string()),
+            Placeholder::Y => logical_monitor.map_or(String::new(), |lm| lm.y.to_string()),
+            Placeholder::Scale => logical_monitor.map_or(String::new(), |lm| lm.scale.to_string()),
+            Placeholder::Rotation => {
+                logical_monitor.map_or(String::new(), |lm| lm.transform.to_string())
+            }
+            Placeholder::Primary => yes_no(logical_monitor.is_some_and(|lm| lm.primary)),
+            Placeholder::ModeWidth => mode.map_or(String::new(), |mode| mode.width.to_string()),
+            Placeholder::ModeHeight => mode.map_or(String::new(), |mode| mode.height.to_string()),
+            Placeholder::ModeRate => {
+                mode.map_or(String::new(), |mode| mode.refresh_rate.to_string())
+            }
+        }
+    }
+}
+
+impl std::str::FromStr for Placeholder {
+    type Err = std::fmt::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Placeholder::ALL
+            .iter()
+            .find(|placeholder| placeholder.to_string() == s)
+            .copied()
+            .ok_or(std::fmt::Error)
+    }
+}
+
+impl std::fmt::Display for Placeholder {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Placeholder::Connector => "connector",
+                Placeholder::Vendor => "vendor",
+                Placeholder::Product => "product",
+                Placeholder::Serial => "serial",
+                Placeholder::Enabled => "enabled",
+                Placeholder::X => "x",
+                Placeholder::Y => "y",
+                Placeholder::Scale => "scale",
+                Placeholder::Rotation => "rotation",
+                Placeholder::Primary => "primary",
+                Placeholder::ModeWidth => "mode.width",
+                Placeholder::ModeHeight => "mode.height",
+                Placeholder::ModeRate => "mode.rate",
+            }
+        )
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum Segment {
+    Literal(String),
+    Placeholder(Placeholder),
+}
+
+pub struct Template {
+    segments: Vec<Segment>,
+}
+
+impl Template {
+    pub fn render(&self, config: &DisplayConfig, physical_monitor: &PhysicalMonitor) -> String {
+        self.segments
+            .iter()
+            .map(|segment| match segment {
+                Segment::Literal(literal) => literal.clone(),
+                Segment::Placeholder(placeholder) => placeholder.value(config, physical_monitor),
+            })
+            .collect()
+    }
+}
+
+impl std::str::FromStr for Template {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut segments = Vec::new();
+        let mut literal = String::new();
+        let mut chars = s.chars().peekable();
+
+        while let Some(c) = chars.next() {
+            match c {
+                '{' if chars.peek() == Some(&'{') => {
+                    chars.next();
+                    literal.push('{');
+                }
+                '}' if chars.peek() == Some(&'}') => {
+                    chars.next();
+                    literal.push('}');
+                }
+                '{' => {
+                    let mut name = String::new();
+                    loop {
+                        match chars.next() {
+                            Some('}') => break,
+                            Some(c) => name.push(c),
+                            None => return Err(Error::Unterminated),
+                        }
+                    }
+
+                    let placeholder = name
+                        .trim()
+                        .parse()
+                        .map_err(|_| Error::UnknownPlaceholder(name.clone()))?;
+                    if !literal.is_empty() {
+                        segments.push(Segment::Literal(std::mem::take(&mut literal)));
+                    }
+                    segments.push(Segment::Placeholder(placeholder));
+                }
+                '}' => return Err(Error::Unterminated),
+                c => literal.push(c),
+            }
+        }
+
+        if !literal.is_empty() {
+            segments.push(Segment::Literal(literal));
+        }
+        Ok(Template { segments })
+    }
+}
+
+/// Either `shell`, or a template applied to each monitor.
+pub enum Format {
+    Shell,
+    Template(Template),
+}
+
+impl std::str::FromStr for Format {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "shell" => Ok(Format::Shell),
+            template => Ok(Format::Template(template.parse()?)),
+        }
+    }
+}
+
+/// Quotes a value so the shell reads it back verbatim.
+fn shell_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "'\\''"))
+}
+
+/// The variable name prefix for a connector, e.g. `DP_1_` for "DP-1".
+fn shell_prefix(connector: &str) -> String {
+    connector
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_uppercase()
+            } else {
+                '_'
+            }
+        })
+        .chain(std::iter::once('_'))
+        .collect()
+}
+
+impl Format {
+    pub fn format<'a>(
+        &self,
+        config: &DisplayConfig,
+        monitors: impl Iterator<Item = &'a PhysicalMonitor>,
+    ) -> String {
+        let monitors: Vec<&PhysicalMonitor> = monitors.collect();
+
+        match self {
+            Format::Template(template) => monitors
+                .iter()
+                .map(|physical_monitor| template.render(config, physical_monitor) + "\n")
+                .collect(),
+            Format::Shell => {
+                let mut output = format!(
+                    "CONNECTORS={}\n",
+                    shell_quote(
+                        &monitors
+                            .iter()
+                            .map(|physical_monitor| physical_monitor.connector.as_str())
+                            .collect::<Vec<_>>()
+                            .join(" ")
+                    )
+                );
+
+                for physical_monitor in monitors.iter() {
+                    let prefix = shell_prefix(&physical_monitor.connector);
+                    for placeholder in Placeholder::ALL.iter() {
+                        output += &format!(
+                            "{}{}={}\n",
+                            prefix,
+                            shell_prefix(&placeholder.to_string()).trim_end_matches('_'),
+                            shell_quote(&placeholder.value(config, physical_monitor))
+                        );
+                    }
+                }
+
+                output
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn segments(template: &str) -> Vec<Segment> {
+        template.parse::<Template>().unwrap().segments
+    }
+
+    fn literal(literal: &str) -> Segment {
+        Segment::Literal(literal.to_string())
+    }
+
+    #[test]
+    fn parses_placeholders_and_literals() {
+        assert_eq!(
+            segments("{connector}: {mode.width}x{mode.height}"),
+            vec![
+                Segment::Placeholder(Placeholder::Connector),
+                literal(": "),
+                Segment::Placeholder(Placeholder::ModeWidth),
+                literal("x"),
+                Segment::Placeholder(Placeholder::ModeHeight),
+            ]
+        );
+        assert_eq!(
+            segments("{ scale }"),
+            vec![Segment::Placeholder(Placeholder::Scale)]
+        );
+        assert_eq!(segments(""), vec![]);
+    }
+
+    #[test]
+    fn unescapes_doubled_braces() {
+        assert_eq!(
+            segments("{{{x}}}"),
+            vec![
+                literal("{"),
+                Segment::Placeholder(Placeholder::X),
+                literal("}"),
+            ]
+        );
+        assert_eq!(segments("{{}}"), vec![literal("{}")]);
+    }
+
+    #[test]
+    fn rejects_bad_templates() {
+        assert!(matches!(
+            "{conector}".parse::<Template>(),
+            Err(Error::UnknownPlaceholder(name)) if name == "conector"
+        ));
+        assert!(matches!(
+            "{connector".parse::<Template>(),
+            Err(Error::Unterminated)
+        ));
+        assert!(matches!(
+            "a}b".parse::<Template>(),
+            Err(Error::Unterminated)
+        ));
+    }
+
+    #[test]
+    fn placeholders_round_trip() {
+        for placeholder in Placeholder::ALL.iter() {
+            assert_eq!(placeholder.to_string().parse(), Ok(*placeholder));
+        }
+    }
+
+    #[test]
+    fn quotes_for_the_shell() {
+        assert_eq!(shell_quote("Dell 27\""), "'Dell 27\"'");
+        assert_eq!(shell_quote("it's"), "'it'\\''s'");
+        assert_eq!(shell_prefix("DP-1"), "DP_1_");
+        assert_eq!(shell_prefix("eDP-1"), "EDP_1_");
+    }
+}
@@ -0,0 +1,126 @@
+use std::fmt::Write;
+
+use gnome_randr::{
+    display_config::{geometry, logical_monitor::Transform, physical_monitor::PhysicalMonitor},
+    DisplayConfig,
+};
+
+// Xwayland doesn't enforce a minimum screen size, and this is its maximum when the monitors don't
+// report one.
+const MIN_SCREEN_SIZE: (i32, i32) = (16, 16);
+const MAX_SCREEN_SIZE: (i32, i32) = (32767, 32767);
+
+/// The geometry of the monitor in the screen, as xrandr reports the CRTC geometry.
+fn geometry(
+    config: &DisplayConfig,
+    physical_monitor: &PhysicalMonitor,
+) -> Option<(i32, i32, i32, i32)> {
+    let logical_monitor = config.search_logical(&physical_monitor.connector)?;
+    let mode = physical_monitor.current_mode()?;
+
+    let (width, height) = geometry::logical_size(
+        mode,
+        logical_monitor.scale,
+        logical_monitor.transform,
+        config.known_properties.layout_mode,
+    );
+
+    Some((width, height, logical_monitor.x, logical_monitor.y))
+}
+
+fn format_monitor(
+    writer: &mut dyn Write,
+    config: &DisplayConfig,
+    physical_monitor: &PhysicalMonitor,
+) -> std::fmt::Result {
+    write!(writer, "{} connected", physical_monitor.connector)?;
+
+    let logical_monitor = config.search_logical(&physical_monitor.connector);
+    if logical_monitor.is_some_and(|logical_monitor| logical_monitor.primary) {
+        write!(writer, " primary")?;
+    }
+
+    if let (Some(logical_monitor), Some((width, height, x, y))) =
+        (logical_monitor, geometry(config, physical_monitor))
+    {
+        write!(writer, " {}x{}+{}+{}", width, height, x, y)?;
+
+        let transform = logical_monitor.transform;
+        let flipped = transform.contains(Transform::FLIPPED);
+        if transform != Transform::NORMAL {
+            write!(writer, " {}", transform - Transform::FLIPPED)?;
+            if flipped {
+                write!(writer, " X axis")?;
+            }
+        }
+
+        write!(writer, " (normal left inverted right x axis y axis)")?;
+
+        if let (Some(width_mm), Some(height_mm)) = (
+            physical_monitor.known_properties.width_mm,
+            physical_monitor.known_properties.height_mm,
+        ) {
+            if transform.contains(Transform::R90) {
+                write!(writer, " {}mm x {}mm", height_mm, width_mm)?;
+            } else {
+                write!(writer, " {}mm x {}mm", width_mm, height_mm)?;
+            }
+        }
+    } else {
+        write!(writer, " (normal left inverted right x axis y axis)")?;
+    }
+    writeln!(writer)?;
+
+    for ((width, height), modes) in physical_monitor.resolutions() {
+        write!(writer, "   {:<12}", format!("{}x{}", width, height))?;
+        for mode in modes {
+            write!(
+                writer,
+                " {:6.2}{}{}",
+                mode.refresh_rate,
+                if mode.known_properties.is_current {
+                    '*'
+                } else {
+                    ' '
+                },
+                if mode.known_properties.is_preferred {
+                    '+'
+                } else {
+                    ' '
+                }
+            )?;
+        }
+        writeln!(writer)?;
+    }
+
+    Ok(())
+}
+
+/// Renders the config the way `xrandr --query` does, so that tools parsing its output keep
+/// working. With a connector, only that monitor is listed after the screen line.
+pub fn format(config: &DisplayConfig, connector: Option<&str>) -> Result<String, std::fmt::Error> {
+    let mut output = String::new();
+
+    let (current_width, current_height) = config
+        .monitors
+        .iter()
+        .filter_map(|physical_monitor| geometry(config, physical_monitor))
+        .fold((0, 0), |(right, bottom), (width, height, x, y)| {
+            (right.max(x + width), bottom.max(y + height))
+        });
+    let (max_width, max_height) = config.max_screen_size().unwrap_or(MAX_SCREEN_SIZE);
+
+    writeln!(
+        output,
+        "Screen 0: minimum {} x {}, current {} x {}, maximum {} x {}",
+        MIN_SCREEN_SIZE.0, MIN_SCREEN_SIZE.1, current_width, current_height, max_width, max_height
+    )?;
+
+    for physical_monitor in config.monitors.iter().filter(|physical_monitor| {
+        connector.is_none_or(|connector| physical_monitor.connector == connector)
+    }) {
+        format_monitor(&mut output, config, physical_monitor)?;
+    }
+
+    Ok(output)
+}
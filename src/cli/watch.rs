@@ -0,0 +1,78 @@
+use dbus::blocking::{Connection, Proxy};
+use gnome_randr::{display_config::events::Event, DisplayConfig};
+use serde_json::json;
+use structopt::StructOpt;
+
+#[derive(StructOpt)]
+pub struct CommandOptions {
+    #[structopt(
+        long,
+        help = "Print each event as a line of JSON",
+        long_help = "Print each event as a line of JSON, e.g. {\"event\":\"moved\",\"connector\":\"DP-1\",\"from\":[1440,0],\"to\":[0,0]}. The \"event\" field is one of connected, disconnected, enabled, disabled, mode-changed, moved, scale-changed, rotated or primary-changed."
+    )]
+    pub json: bool,
+}
+
+fn to_json(event: &Event) -> serde_json::Value {
+    match event {
+        Event::Connected {
+            connector,
+            vendor,
+            product,
+            serial,
+        } => json!({
+            "event": "connected",
+            "connector": connector,
+            "vendor": vendor,
+            "product": product,
+            "serial": serial,
+        }),
+        Event::Disconnected { connector } => {
+            json!({ "event": "disconnected", "connector": connector })
+        }
+        Event::Enabled { connector } => json!({ "event": "enabled", "connector": connector }),
+        Event::Disabled { connector } => json!({ "event": "disabled", "connector": connector }),
+        Event::ModeChanged {
+            connector,
+            from,
+            to,
+        } => json!({ "event": "mode-changed", "connector": connector, "from": from, "to": to }),
+        Event::Moved {
+            connector,
+            from,
+            to,
+        } => json!({ "event": "moved", "connector": connector, "from": from, "to": to }),
+        Event::ScaleChanged {
+            connector,
+            from,
+            to,
+        } => json!({ "event": "scale-changed", "connector": connector, "from": from, "to": to }),
+        Event::Rotated {
+            connector,
+            from,
+            to,
+        } => json!({
+            "event": "rotated",
+            "connector": connector,
+            "from": from.to_string(),
+            "to": to.to_string(),
+        }),
+        Event::PrimaryChanged { from, to } => {
+            json!({ "event": "primary-changed", "from": from, "to": to })
+        }
+    }
+}
+
+pub fn handle(
+    opts: &CommandOptions,
+    proxy: &Proxy<&Connection>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    Ok(DisplayConfig::watch(proxy, |event, _| {
+        if opts.json {
+            println!("{}", to_json(event));
+        } else {
+            println!("{}", event);
+        }
+        true
+    })?)
+}
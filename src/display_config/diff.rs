@@ -0,0 +1,354 @@
+use super::{logical_monitor::Transform, ApplyConfig, DisplayConfig};
+
+/// How a single physical monitor is shown, as far as a configuration change is concerned.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MonitorState {
+    pub x: i32,
+    pub y: i32,
+    pub mode_id: String,
+    pub scale: f64,
+    pub transform: Transform,
+    pub primary: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Change {
+    Enabled(MonitorState),
+    Disabled,
+    Position { from: (i32, i32), to: (i32, i32) },
+    Mode { from: String, to: String },
+    Scale { from: f64, to: f64 },
+    Transform { from: Transform, to: Transform },
+    Primary(bool),
+}
+
+impl std::fmt::Display for Change {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Change::Enabled(state) => write!(
+                f,
+                "enabled at {}x{} with mode {}, scale {}, rotation {}{}",
+                state.x,
+                state.y,
+                state.mode_id,
+                state.scale,
+                state.transform,
+                if state.primary { ", primary" } else { "" }
+            ),
+            Change::Disabled => write!(f, "disabled"),
+            Change::Position { from, to } => {
+                write!(f, "position {}x{} -> {}x{}", from.0, from.1, to.0, to.1)
+            }
+            Change::Mode { from, to } => write!(f, "mode {} -> {}", from, to),
+            Change::Scale { from, to } => write!(f, "scale {} -> {}", from, to),
+            Change::Transform { from, to } => write!(f, "rotation {} -> {}", from, to),
+            Change::Primary(true) => write!(f, "becomes primary"),
+            Change::Primary(false) => write!(f, "no longer primary"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct MonitorDiff {
+    pub connector: String,
+    pub changes: Vec<Change>,
+}
+
+/// The changes a new configuration makes to each physical monitor. Monitors that don't change are
+/// left out.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConfigDiff {
+    pub monitors: Vec<MonitorDiff>,
+}
+
+fn config_states(config: &DisplayConfig) -> Vec<(String, Option<MonitorState>)> {
+    config
+        .monitors
+        .iter()
+        .map(|physical_monitor| {
+            let state =
+                config
+                    .search_logical(&physical_monitor.connector)
+                    .and_then(|logical_monitor| {
+                        Some(MonitorState {
+                            x: logical_monitor.x,
+                            y: logical_monitor.y,
+                            mode_id: physical_monitor.current_mode()?.id.clone(),
+                            scale: logical_monitor.scale,
+                            transform: logical_monitor.transform,
+                            primary: logical_monitor.primary,
+                        })
+                    });
+            (physical_monitor.connector.clone(), state)
+        })
+        .collect()
+}
+
+fn apply_states(configs: &[ApplyConfig]) -> Vec<(String, Option<MonitorState>)> {
+    configs
+        .iter()
+        .flat_map(|apply_config| {
+            apply_config.monitors.iter().map(move |monitor| {
+                (
+                    monitor.connector.to_string(),
+                    Some(MonitorState {
+                        x: apply_config.x_pos,
+                        y: apply_config.y_pos,
+                        mode_id: monitor.mode_id.to_string(),
+                        scale: apply_config.scale,
+                        transform: Transform::from_bits_truncate(apply_config.transform),
+                        primary: apply_config.primary,
+                    }),
+                )
+            })
+        })
+        .collect()
+}
+
+fn changes(old: &Option<MonitorState>, new: &Option<MonitorState>) -> Vec<Change> {
+    let (old, new) = match (old, new) {
+        (None, None) => return Vec::new(),
+        (None, Some(new)) => return vec![Change::Enabled(new.clone())],
+        (Some(_), None) => return vec![Change::Disabled],
+        (Some(old), Some(new)) => (old, new),
+    };
+
+    let mut changes = Vec::new();
+    if (old.x, old.y) != (new.x, new.y) {
+        changes.push(Change::Position {
+            from: (old.x, old.y),
+            to: (new.x, new.y),
+        });
+    }
+    if old.mode_id != new.mode_id {
+        changes.push(Change::Mode {
+            from: old.mode_id.clone(),
+            to: new.mode_id.clone(),
+        });
+    }
+    if old.scale != new.scale {
+        changes.push(Change::Scale {
+            from: old.scale,
+            to: new.scale,
+        });
+    }
+    if old.transform != new.transform {
+        changes.push(Change::Transform {
+            from: old.transform,
+            to: new.transform,
+        });
+    }
+    if old.primary != new.primary {
+        changes.push(Change::Primary(new.primary));
+    }
+    changes
+}
+
+impl ConfigDiff {
+    fn from_states(
+        old: Vec<(String, Option<MonitorState>)>,
+        new: Vec<(String, Option<MonitorState>)>,
+    ) -> ConfigDiff {
+        // Monitors missing from either side are disabled there, e.g. because they were unplugged.
+        let mut connectors: Vec<&str> = Vec::new();
+        for (connector, _) in old.iter().chain(new.iter()) {
+            if !connectors.contains(&connector.as_str()) {
+                connectors.push(connector);
+            }
+        }
+
+        let state = |states: &[(String, Option<MonitorState>)], connector: &str| {
+            states
+                .iter()
+                .find(|(other, _)| other == connector)
+                .and_then(|(_, state)| state.clone())
+        };
+
+        ConfigDiff {
+            monitors: connectors
+                .into_iter()
+                .map(|connector| MonitorDiff {
+                    connector: connector.to_string(),
+                    changes: changes(&state(&old, connector), &state(&new, connector)),
+                })
+                .filter(|monitor_diff| !monitor_diff.changes.is_empty())
+                .collect(),
+        }
+    }
+
+    /// The changes applying `configs` would make to `config`.
+    pub fn new(config: &DisplayConfig, configs: &[ApplyConfig]) -> ConfigDiff {
+        ConfigDiff::from_states(config_states(config), apply_states(configs))
+    }
+
+    /// The changes between two states of the display server, e.g. before and after a hotplug.
+    pub fn between(old: &DisplayConfig, new: &DisplayConfig) -> ConfigDiff {
+        ConfigDiff::from_states(config_states(old), config_states(new))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.monitors.is_empty()
+    }
+}
+
+impl std::fmt::Display for ConfigDiff {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for monitor_diff in self.monitors.iter() {
+            for change in monitor_diff.changes.iter() {
+                writeln!(f, "{}: {}", monitor_diff.connector, change)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::display_config::testing;
+
+    fn diff_of(connector: &str, changes: Vec<Change>) -> MonitorDiff {
+        MonitorDiff {
+            connector: connector.to_string(),
+            changes,
+        }
+    }
+
+    #[test]
+    fn same_configs_have_no_changes() {
+        let diff = ConfigDiff::between(
+            &testing::laptop_and_monitor(),
+            &testing::laptop_and_monitor(),
+        );
+        assert!(diff.is_empty());
+        assert_eq!(diff.to_string(), "");
+    }
+
+    #[test]
+    fn lists_changes_per_monitor() {
+        let old = testing::laptop_and_monitor();
+        // The monitor goes above the laptop, rotated, and takes over as primary.
+        let new = testing::config(&[
+            (0, 2560, 1.5, 0, false, &["eDP-1"]),
+            (0, 0, 1.0, 1, true, &["DP-1"]),
+        ]);
+
+        let diff = ConfigDiff::between(&old, &new);
+        assert_eq!(
+            diff.monitors,
+            vec![
+                diff_of(
+                    "eDP-1",
+                    vec![
+                        Change::Position {
+                            from: (0, 0),
+                            to: (0, 2560)
+                        },
+                        Change::Scale { from: 2.0, to: 1.5 },
+                        Change::Primary(false),
+                    ]
+                ),
+                diff_of(
+                    "DP-1",
+                    vec![
+                        Change::Position {
+                            from: (1440, 0),
+                            to: (0, 0)
+                        },
+                        Change::Transform {
+                            from: Transform::NORMAL,
+                            to: Transform::R90
+                        },
+                        Change::Primary(true),
+                    ]
+                ),
+            ]
+        );
+        assert_eq!(
+            diff.to_string(),
+            "eDP-1: position 0x0 -> 0x2560\n\
+             eDP-1: scale 2 -> 1.5\n\
+             eDP-1: no longer primary\n\
+             DP-1: position 1440x0 -> 0x0\n\
+             DP-1: rotation normal -> right\n\
+             DP-1: becomes primary\n"
+        );
+    }
+
+    #[test]
+    fn reports_enabled_disabled_and_mode_changes() {
+        let old = testing::laptop_and_monitor();
+        let mut new = testing::config(&[
+            (0, 0, 2.0, 0, true, &["eDP-1"]),
+            (1440, 0, 1.0, 0, false, &["HDMI-1"]),
+        ]);
+        new.monitors[0].modes[0].known_properties.is_current = false;
+        new.monitors[0].modes[1].known_properties.is_current = true;
+        new.monitors[2].modes[0].known_properties.is_current = true;
+
+        assert_eq!(
+            ConfigDiff::between(&old, &new).monitors,
+            vec![
+                diff_of(
+                    "eDP-1",
+                    vec![Change::Mode {
+                        from: "2880x1800@60.000".to_string(),
+                        to: "1920x1200@59.950".to_string()
+                    }]
+                ),
+                diff_of("DP-1", vec![Change::Disabled]),
+                diff_of(
+                    "HDMI-1",
+                    vec![Change::Enabled(MonitorState {
+                        x: 1440,
+                        y: 0,
+                        mode_id: "1920x1080@60.000".to_string(),
+                        scale: 1.0,
+                        transform: Transform::NORMAL,
+                        primary: false,
+                    })]
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn unplugged_monitors_are_disabled() {
+        let old = testing::laptop_and_monitor();
+        let mut new = testing::config(&[(0, 0, 2.0, 0, true, &["eDP-1"])]);
+        new.monitors.retain(|monitor| monitor.connector != "DP-1");
+
+        assert_eq!(
+            ConfigDiff::between(&old, &new).monitors,
+            vec![diff_of("DP-1", vec![Change::Disabled])]
+        );
+        assert!(ConfigDiff::between(&new, &new).is_empty());
+    }
+
+    #[test]
+    fn diffs_against_apply_configs() {
+        let config = testing::laptop_and_monitor();
+        let mut configs: Vec<ApplyConfig> = config
+            .logical_monitors
+            .iter()
+            .map(|logical_monitor| ApplyConfig::from(logical_monitor, &config.monitors))
+            .collect();
+        assert!(ConfigDiff::new(&config, &configs).is_empty());
+
+        configs[1].monitors[0].mode_id = "2560x1440@143.912";
+        configs.remove(0);
+        assert_eq!(
+            ConfigDiff::new(&config, &configs).monitors,
+            vec![
+                diff_of("eDP-1", vec![Change::Disabled]),
+                diff_of(
+                    "DP-1",
+                    vec![Change::Mode {
+                        from: "2560x1440@59.951".to_string(),
+                        to: "2560x1440@143.912".to_string()
+                    }]
+                ),
+            ]
+        );
+    }
+}
@@ -0,0 +1,378 @@
+use std::{
+    collections::VecDeque,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+use dbus::{
+    blocking::{Connection, Proxy},
+    channel::Token,
+};
+
+use super::{
+    diff::{Change, ConfigDiff},
+    logical_monitor::Transform,
+    DisplayConfig,
+};
+
+type Result<T> = std::prelude::rust_2021::Result<T, dbus::Error>;
+
+/// Something that changed about the monitors between two states of the display server.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Event {
+    Connected {
+        connector: String,
+        vendor: String,
+        product: String,
+        serial: String,
+    },
+    Disconnected {
+        connector: String,
+    },
+    Enabled {
+        connector: String,
+    },
+    Disabled {
+        connector: String,
+    },
+    ModeChanged {
+        connector: String,
+        from: String,
+        to: String,
+    },
+    Moved {
+        connector: String,
+        from: (i32, i32),
+        to: (i32, i32),
+    },
+    ScaleChanged {
+        connector: String,
+        from: f64,
+        to: f64,
+    },
+    Rotated {
+        connector: String,
+        from: Transform,
+        to: Transform,
+    },
+    /// The connectors of the primary logical monitor's first monitor, if there is one.
+    PrimaryChanged {
+        from: Option<String>,
+        to: Option<String>,
+    },
+}
+
+impl std::fmt::Display for Event {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Event::Connected {
+                connector,
+                vendor,
+                product,
+                serial,
+            } => write!(
+                f,
+                "{}: connected ({} {} {})",
+                connector, vendor, product, serial
+            ),
+            Event::Disconnected { connector } => write!(f, "{}: disconnected", connector),
+            Event::Enabled { connector } => write!(f, "{}: enabled", connector),
+            Event::Disabled { connector } => write!(f, "{}: disabled", connector),
+            Event::ModeChanged {
+                connector,
+                from,
+                to,
+            } => write!(f, "{}: mode {} -> {}", connector, from, to),
+            Event::Moved {
+                connector,
+                from,
+                to,
+            } => write!(
+                f,
+                "{}: moved {}x{} -> {}x{}",
+                connector, from.0, from.1, to.0, to.1
+            ),
+            Event::ScaleChanged {
+                connector,
+                from,
+                to,
+            } => write!(f, "{}: scale {} -> {}", connector, from, to),
+            Event::Rotated {
+                connector,
+                from,
+                to,
+            } => write!(f, "{}: rotation {} -> {}", connector, from, to),
+            Event::PrimaryChanged { from, to } => write!(
+                f,
+                "primary: {} -> {}",
+                from.as_deref().unwrap_or("none"),
+                to.as_deref().unwrap_or("none")
+            ),
+        }
+    }
+}
+
+fn primary_connector(config: &DisplayConfig) -> Option<String> {
+    config
+        .logical_monitors
+        .iter()
+        .find(|logical_monitor| logical_monitor.primary)
+        .and_then(|logical_monitor| logical_monitor.monitors.first())
+        .map(|monitor| monitor.connector.clone())
+}
+
+impl Event {
+    /// The events that turn `old` into `new`: monitors being plugged in or out first, then the
+    /// changes to each monitor, then the primary monitor.
+    pub fn between(old: &DisplayConfig, new: &DisplayConfig) -> Vec<Event> {
+        let mut events = Vec::new();
+
+        for physical_monitor in new.monitors.iter() {
+            if old.search_physical(&physical_monitor.connector).is_none() {
+                events.push(Event::Connected {
+                    connector: physical_monitor.connector.clone(),
+                    vendor: physical_monitor.vendor.clone(),
+                    product: physical_monitor.product.clone(),
+                    serial: physical_monitor.serial.clone(),
+                });
+            }
+        }
+        for physical_monitor in old.monitors.iter() {
+            if new.search_physical(&physical_monitor.connector).is_none() {
+                events.push(Event::Disconnected {
+                    connector: physical_monitor.connector.clone(),
+                });
+            }
+        }
+
+        for monitor_diff in ConfigDiff::between(old, new).monitors {
+            let connector = monitor_diff.connector;
+            events.extend(monitor_diff.changes.into_iter().filter_map(|change| {
+                let connector = connector.clone();
+                Some(match change {
+                    // Plugging a monitor in or out already says it was turned on or off.
+                    Change::Enabled(_) if old.search_physical(&connector).is_none() => return None,
+                    Change::Disabled if new.search_physical(&connector).is_none() => return None,
+                    Change::Enabled(_) => Event::Enabled { connector },
+                    Change::Disabled => Event::Disabled { connector },
+                    Change::Position { from, to } => Event::Moved {
+                        connector,
+                        from,
+                        to,
+                    },
+                    Change::Mode { from, to } => Event::ModeChanged {
+                        connector,
+                        from,
+                        to,
+                    },
+                    Change::Scale { from, to } => Event::ScaleChanged {
+                        connector,
+                        from,
+                        to,
+                    },
+                    Change::Transform { from, to } => Event::Rotated {
+                        connector,
+                        from,
+                        to,
+                    },
+                    // Reported once for the whole layout below.
+                    Change::Primary(_) => return None,
+                })
+            }));
+        }
+
+        let (from, to) = (primary_connector(old), primary_connector(new));
+        if from != to {
+            events.push(Event::PrimaryChanged { from, to });
+        }
+
+        events
+    }
+}
+
+/// Yields an event for every change to the monitors, fetching the state again whenever the display
+/// server says the monitors changed. Blocks until there is an event to yield.
+pub struct Watcher<'a> {
+    proxy: &'a Proxy<'a, &'a Connection>,
+    config: DisplayConfig,
+    changed: Arc<AtomicBool>,
+    token: Option<Token>,
+    pending: VecDeque<Event>,
+}
+
+impl<'a> Watcher<'a> {
+    pub fn new(proxy: &'a Proxy<'a, &'a Connection>) -> Result<Watcher<'a>> {
+        let changed = Arc::new(AtomicBool::new(false));
+        let token = DisplayConfig::on_monitors_changed(proxy, {
+            let changed = changed.clone();
+            move || {
+                changed.store(true, Ordering::SeqCst);
+                true
+            }
+        })?;
+
+        Ok(Watcher {
+            proxy,
+            config: DisplayConfig::get_current_state(proxy)?,
+            changed,
+            token: Some(token),
+            pending: VecDeque::new(),
+        })
+    }
+
+    /// The state as of the last event yielded.
+    pub fn config(&self) -> &DisplayConfig {
+        &self.config
+    }
+}
+
+impl Iterator for Watcher<'_> {
+    type Item = Result<Event>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(event) = self.pending.pop_front() {
+                return Some(Ok(event));
+            }
+
+            if let Err(error) = self.proxy.connection.process(Duration::from_millis(1000)) {
+                return Some(Err(error));
+            }
+
+            if self.changed.swap(false, Ordering::SeqCst) {
+                let config = match DisplayConfig::get_current_state(self.proxy) {
+                    Ok(config) => config,
+                    Err(error) => return Some(Err(error)),
+                };
+                self.pending.extend(Event::between(&self.config, &config));
+                self.config = config;
+            }
+        }
+    }
+}
+
+impl Drop for Watcher<'_> {
+    fn drop(&mut self) {
+        if let Some(token) = self.token.take() {
+            let _ = self.proxy.connection.remove_match(token);
+        }
+    }
+}
+
+impl DisplayConfig {
+    /// Calls `callback` with every change to the monitors, along with the state after it, for as
+    /// long as it returns `true`.
+    pub fn watch<F>(proxy: &Proxy<&Connection>, mut callback: F) -> Result<()>
+    where
+        F: FnMut(&Event, &DisplayConfig) -> bool,
+    {
+        let mut watcher = Watcher::new(proxy)?;
+        while let Some(event) = watcher.next() {
+            if !callback(&event?, watcher.config()) {
+                break;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::display_config::testing;
+
+    /// The test monitors as seen with the projector unplugged.
+    fn without_projector(mut config: DisplayConfig) -> DisplayConfig {
+        config
+            .monitors
+            .retain(|physical_monitor| physical_monitor.connector != "HDMI-1");
+        config
+    }
+
+    /// The test monitors with the projector turned on to the right of the others.
+    fn with_projector() -> DisplayConfig {
+        let mut config = testing::config(&[
+            (0, 0, 2.0, 0, true, &["eDP-1"]),
+            (1440, 0, 1.0, 0, false, &["DP-1"]),
+            (4000, 0, 1.0, 0, false, &["HDMI-1"]),
+        ]);
+        config.monitors[2].modes[0].known_properties.is_current = true;
+        config
+    }
+
+    #[test]
+    fn reports_monitors_being_plugged_in() {
+        let old = without_projector(testing::laptop_and_monitor());
+        assert_eq!(
+            Event::between(&old, &with_projector()),
+            vec![Event::Connected {
+                connector: "HDMI-1".to_string(),
+                vendor: "EPS".to_string(),
+                product: "EPSON PJ".to_string(),
+                serial: "0x01010101".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn reports_monitors_being_unplugged() {
+        let new = without_projector(testing::laptop_and_monitor());
+        assert_eq!(
+            Event::between(&with_projector(), &new),
+            vec![Event::Disconnected {
+                connector: "HDMI-1".to_string()
+            }]
+        );
+    }
+
+    #[test]
+    fn reports_enabling_a_connected_monitor() {
+        assert_eq!(
+            Event::between(&testing::laptop_and_monitor(), &with_projector()),
+            vec![Event::Enabled {
+                connector: "HDMI-1".to_string()
+            }]
+        );
+    }
+
+    #[test]
+    fn reports_moves_and_scale_changes() {
+        let new = testing::config(&[
+            (0, 0, 1.5, 0, true, &["eDP-1"]),
+            (1920, 0, 1.0, 0, false, &["DP-1"]),
+        ]);
+        assert_eq!(
+            Event::between(&testing::laptop_and_monitor(), &new),
+            vec![
+                Event::ScaleChanged {
+                    connector: "eDP-1".to_string(),
+                    from: 2.0,
+                    to: 1.5,
+                },
+                Event::Moved {
+                    connector: "DP-1".to_string(),
+                    from: (1440, 0),
+                    to: (1920, 0),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn reports_the_primary_monitor_once() {
+        let new = testing::config(&[
+            (0, 0, 2.0, 0, false, &["eDP-1"]),
+            (1440, 0, 1.0, 0, true, &["DP-1"]),
+        ]);
+        assert_eq!(
+            Event::between(&testing::laptop_and_monitor(), &new),
+            vec![Event::PrimaryChanged {
+                from: Some("eDP-1".to_string()),
+                to: Some("DP-1".to_string()),
+            }]
+        );
+    }
+}
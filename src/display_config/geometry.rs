@@ -0,0 +1,288 @@
+use super::{
+    logical_monitor::{LogicalMonitor, Transform},
+    physical_monitor::Mode,
+    DisplayConfig, LayoutMode,
+};
+
+/// An area of the global screen space, in layout coordinates.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rect {
+    pub x: i32,
+    pub y: i32,
+    pub width: i32,
+    pub height: i32,
+}
+
+impl Rect {
+    pub fn right(&self) -> i32 {
+        self.x + self.width
+    }
+
+    pub fn bottom(&self) -> i32 {
+        self.y + self.height
+    }
+
+    pub fn contains(&self, x: i32, y: i32) -> bool {
+        x >= self.x && x < self.right() && y >= self.y && y < self.bottom()
+    }
+
+    /// Whether the rects share any area. Rects that only touch along an edge don't intersect.
+    pub fn intersects(&self, other: &Rect) -> bool {
+        self.x < other.right()
+            && other.x < self.right()
+            && self.y < other.bottom()
+            && other.y < self.bottom()
+    }
+
+    /// The smallest rect containing both rects.
+    pub fn union(&self, other: &Rect) -> Rect {
+        let x = self.x.min(other.x);
+        let y = self.y.min(other.y);
+        Rect {
+            x,
+            y,
+            width: self.right().max(other.right()) - x,
+            height: self.bottom().max(other.bottom()) - y,
+        }
+    }
+
+    /// The edge of this rect that `other` touches, and the length of the edge they share. Rects
+    /// that only meet at a corner don't touch.
+    pub fn touching_edge(&self, other: &Rect) -> Option<(Side, i32)> {
+        let overlap = |a_start: i32, a_end: i32, b_start: i32, b_end: i32| {
+            a_end.min(b_end) - a_start.max(b_start)
+        };
+        let vertical = overlap(self.y, self.bottom(), other.y, other.bottom());
+        let horizontal = overlap(self.x, self.right(), other.x, other.right());
+
+        let (side, length) = if other.right() == self.x {
+            (Side::Left, vertical)
+        } else if other.x == self.right() {
+            (Side::Right, vertical)
+        } else if other.bottom() == self.y {
+            (Side::Top, horizontal)
+        } else if other.y == self.bottom() {
+            (Side::Bottom, horizontal)
+        } else {
+            return None;
+        };
+
+        if length > 0 {
+            Some((side, length))
+        } else {
+            None
+        }
+    }
+}
+
+impl std::fmt::Display for Rect {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}x{}+{}+{}", self.width, self.height, self.x, self.y)
+    }
+}
+
+/// An edge of a logical monitor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Side {
+    Left,
+    Right,
+    Top,
+    Bottom,
+}
+
+impl std::fmt::Display for Side {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Side::Left => "left",
+                Side::Right => "right",
+                Side::Top => "top",
+                Side::Bottom => "bottom",
+            }
+        )
+    }
+}
+
+/// A logical monitor touching the one passed to `DisplayConfig::neighbours`, along with the edge of
+/// that monitor it touches (i.e. which side of it the neighbour is on) and the length of the edge
+/// they share.
+#[derive(Debug)]
+pub struct Neighbour<'a> {
+    pub logical_monitor: &'a LogicalMonitor,
+    pub side: Side,
+    pub overlap: i32,
+}
+
+/// The size a logical monitor showing `mode` takes up in the global screen space. In the logical
+/// layout mode the mode is divided by the scale, and rotating by 90° or 270° swaps the dimensions.
+pub fn logical_size(
+    mode: &Mode,
+    scale: f64,
+    transform: Transform,
+    layout_mode: LayoutMode,
+) -> (i32, i32) {
+    let (width, height) = match layout_mode {
+        LayoutMode::Logical => (
+            (mode.width as f64 / scale).round() as i32,
+            (mode.height as f64 / scale).round() as i32,
+        ),
+        LayoutMode::Physical => (mode.width, mode.height),
+    };
+
+    if transform.contains(Transform::R90) {
+        (height, width)
+    } else {
+        (width, height)
+    }
+}
+
+impl DisplayConfig {
+    /// The area covered by every logical monitor, or `None` if every monitor is disabled.
+    pub fn bounding_box(&self) -> Option<Rect> {
+        self.logical_monitors
+            .iter()
+            .filter_map(|logical_monitor| logical_monitor.rect(self))
+            .reduce(|bounds, rect| bounds.union(&rect))
+    }
+
+    /// The logical monitor covering the given point in the global screen space.
+    pub fn monitor_at(&self, x: i32, y: i32) -> Option<&LogicalMonitor> {
+        self.logical_monitors.iter().find(|logical_monitor| {
+            logical_monitor
+                .rect(self)
+                .is_some_and(|rect| rect.contains(x, y))
+        })
+    }
+
+    /// The logical monitors touching the one the given connector belongs to.
+    pub fn neighbours(&self, connector: &str) -> Vec<Neighbour<'_>> {
+        let own = match self
+            .search_logical(connector)
+            .and_then(|logical_monitor| Some((logical_monitor, logical_monitor.rect(self)?)))
+        {
+            Some(own) => own,
+            None => return Vec::new(),
+        };
+
+        self.logical_monitors
+            .iter()
+            .filter(|logical_monitor| !std::ptr::eq(*logical_monitor, own.0))
+            .filter_map(|logical_monitor| {
+                let (side, overlap) = own.1.touching_edge(&logical_monitor.rect(self)?)?;
+                Some(Neighbour {
+                    logical_monitor,
+                    side,
+                    overlap,
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::display_config::testing;
+
+    fn rect(x: i32, y: i32, width: i32, height: i32) -> Rect {
+        Rect {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+
+    #[test]
+    fn finds_the_touching_edge() {
+        let own = rect(0, 0, 1440, 900);
+
+        assert_eq!(
+            own.touching_edge(&rect(1440, 0, 2560, 1440)),
+            Some((Side::Right, 900))
+        );
+        assert_eq!(
+            own.touching_edge(&rect(-1920, 100, 1920, 1080)),
+            Some((Side::Left, 800))
+        );
+        assert_eq!(
+            own.touching_edge(&rect(200, -1080, 1920, 1080)),
+            Some((Side::Top, 1240))
+        );
+        assert_eq!(
+            own.touching_edge(&rect(0, 900, 1000, 500)),
+            Some((Side::Bottom, 1000))
+        );
+    }
+
+    #[test]
+    fn corners_gaps_and_overlaps_dont_touch() {
+        let own = rect(0, 0, 1440, 900);
+
+        assert_eq!(own.touching_edge(&rect(1440, 900, 100, 100)), None);
+        assert_eq!(own.touching_edge(&rect(1441, 0, 100, 100)), None);
+        assert_eq!(own.touching_edge(&rect(100, 100, 100, 100)), None);
+    }
+
+    #[test]
+    fn computes_logical_sizes() {
+        let config = testing::laptop_and_monitor();
+        let mode = config
+            .search_physical("eDP-1")
+            .unwrap()
+            .current_mode()
+            .unwrap();
+
+        let size =
+            |scale, transform, layout_mode| logical_size(mode, scale, transform, layout_mode);
+        assert_eq!(
+            size(2.0, Transform::NORMAL, LayoutMode::Logical),
+            (1440, 900)
+        );
+        assert_eq!(size(1.5, Transform::R90, LayoutMode::Logical), (1200, 1920));
+        assert_eq!(
+            size(
+                2.0,
+                Transform::R270 | Transform::FLIPPED,
+                LayoutMode::Physical
+            ),
+            (1800, 2880)
+        );
+    }
+
+    #[test]
+    fn finds_neighbours() {
+        let config = testing::laptop_and_monitor();
+
+        let neighbours = config.neighbours("eDP-1");
+        assert_eq!(neighbours.len(), 1);
+        assert_eq!(neighbours[0].logical_monitor.monitors[0].connector, "DP-1");
+        assert_eq!(neighbours[0].side, Side::Right);
+        assert_eq!(neighbours[0].overlap, 900);
+
+        let neighbours = config.neighbours("DP-1");
+        assert_eq!(neighbours.len(), 1);
+        assert_eq!(neighbours[0].side, Side::Left);
+
+        // Disabled monitors have no neighbours.
+        assert!(config.neighbours("HDMI-1").is_empty());
+    }
+
+    #[test]
+    fn finds_monitors_and_bounds() {
+        let config = testing::config(&[
+            (0, 1440, 2.0, 0, true, &["eDP-1"]),
+            (0, 0, 1.0, 0, false, &["DP-1"]),
+        ]);
+
+        assert_eq!(config.bounding_box(), Some(rect(0, 0, 2560, 2340)));
+        assert_eq!(
+            config.monitor_at(100, 1500).unwrap().monitors[0].connector,
+            "eDP-1"
+        );
+        assert!(config.monitor_at(2000, 2000).is_none());
+        assert_eq!(config.neighbours("DP-1")[0].side, Side::Bottom);
+    }
+}
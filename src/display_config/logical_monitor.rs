@@ -2,6 +2,11 @@ use std::fmt::{self};
 
 use bitflags::bitflags;
 
+use super::{
+    geometry::{self, Rect},
+    properties, DisplayConfig,
+};
+
 // monitors displaying this logical monitor
 #[derive(Debug, Clone)]
 pub struct Monitor {
@@ -146,11 +151,7 @@ impl LogicalMonitor {
             scale: result.2,
             transform: Transform::from_bits_truncate(result.3),
             primary: result.4,
-            monitors: result
-                .5
-                .into_iter()
-                .map(Monitor::from)
-                .collect(),
+            monitors: result.5.into_iter().map(Monitor::from).collect(),
             properties: result.6,
         }
     }
@@ -186,6 +187,35 @@ impl LogicalMonitor {
     }
 }
 
+impl LogicalMonitor {
+    /// The size the logical monitor takes up in the global screen space, based on the current mode
+    /// of its first monitor. Mirrored monitors always share a resolution.
+    pub fn logical_size(&self, config: &DisplayConfig) -> Option<(i32, i32)> {
+        let mode = self
+            .monitors
+            .first()
+            .and_then(|monitor| config.search_physical(&monitor.connector))
+            .and_then(|physical_monitor| physical_monitor.current_mode())?;
+
+        Some(geometry::logical_size(
+            mode,
+            self.scale,
+            self.transform,
+            config.known_properties.layout_mode,
+        ))
+    }
+
+    pub fn rect(&self, config: &DisplayConfig) -> Option<Rect> {
+        let (width, height) = self.logical_size(config)?;
+        Some(Rect {
+            x: self.x,
+            y: self.y,
+            width,
+            height,
+        })
+    }
+}
+
 impl std::fmt::Display for LogicalMonitor {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         // x: 0 y: 820, scale: 1.0, rotation: normal, primary: no
@@ -208,8 +238,6 @@ impl std::fmt::Display for LogicalMonitor {
             writeln!(f, "\t{}", monitor)?
         }
 
-        // TODO: Print properties?
-
-        Ok(())
+        properties::format_properties(f, &self.properties)
     }
 }
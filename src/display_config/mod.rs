@@ -1,7 +1,15 @@
+pub mod diff;
+pub mod events;
+pub mod geometry;
 pub mod logical_monitor;
 pub mod physical_monitor;
+mod properties;
 pub mod proxied_methods;
 mod raw;
+pub mod selector;
+#[doc(hidden)]
+pub mod testing;
+pub mod validation;
 
 use logical_monitor::LogicalMonitor;
 use physical_monitor::PhysicalMonitor;
@@ -10,7 +18,7 @@ pub use proxied_methods::{ApplyConfig, ApplyMonitor};
 // Config properties/comments are sourced from https://github.com/jadahl/gnome-monitor-config/blob/master/src/org.gnome.Mutter.DisplayConfig.xml
 
 /// Current layout mode represents the way logical monitors are layed out on the screen.
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum LayoutMode {
     /// The dimension of a logical monitor is the dimension of the monitor mode, divided by the logical monitor scale.
     Logical,
@@ -25,6 +33,25 @@ impl LayoutMode {
             _ => LayoutMode::Logical,
         }
     }
+
+    fn bits(&self) -> u32 {
+        match self {
+            LayoutMode::Logical => 1,
+            LayoutMode::Physical => 2,
+        }
+    }
+}
+
+impl std::str::FromStr for LayoutMode {
+    type Err = std::fmt::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "logical" => Ok(LayoutMode::Logical),
+            "physical" => Ok(LayoutMode::Physical),
+            _ => Err(std::fmt::Error),
+        }
+    }
 }
 
 impl std::fmt::Display for LayoutMode {
@@ -51,7 +78,7 @@ const KNOWN_PROPERTY_KEYS: [&str; 4] = [
 pub struct KnownProperties {
     pub supports_mirroring: bool,
     /** Represents in what way logical monitors are laid out on the screen. The layout mode can be either of the ones listed below.
-     * Absence of this property means the layout mode cannot be changed, and that "logical" mode is assumed to be used. TODO: implement this
+     * Absence of this property means the layout mode cannot be changed, and that "logical" mode is assumed to be used.
      *   - 1 : logical  - the dimension of a logical monitor is derived from the monitor modes associated with it, then scaled using the logical monitor scale.
      *   - 2 : physical - the dimension of a logical monitor is derived from the monitor modes associated with it.
      */
@@ -64,13 +91,7 @@ pub struct KnownProperties {
 
 impl KnownProperties {
     fn from(result: &dbus::arg::PropMap) -> KnownProperties {
-        let as_bool = |prop: &str| -> Option<bool> {
-            match result.get(prop).map(|val| val.0.as_u64()).flatten() {
-                Some(1) => Some(true),
-                Some(0) => Some(false),
-                _ => None,
-            }
-        };
+        let as_bool = |prop: &str| properties::get_bool(result, prop);
 
         KnownProperties {
             supports_mirroring: as_bool("supports-mirroring").unwrap_or(true),
@@ -145,19 +166,35 @@ impl DisplayConfig {
         }
     }
 
-    pub fn search(&self, connector: &str) -> Option<(&LogicalMonitor, &PhysicalMonitor)> {
-        let physical_monitor = self
-            .monitors
+    /// Finds a connected physical monitor, whether or not it is currently part of a logical monitor.
+    pub fn search_physical(&self, connector: &str) -> Option<&PhysicalMonitor> {
+        self.monitors
             .iter()
-            .find(|monitor| monitor.connector == *connector);
+            .find(|monitor| monitor.connector == *connector)
+    }
 
-        let logical_monitor = self.logical_monitors.iter().find(|monitor| {
-            monitor
-                .monitors
-                .iter()
-                .find(|pm| pm.connector == *connector)
-                .is_some()
-        });
+    /// Finds the logical monitor the given connector belongs to. Returns `None` for disabled monitors.
+    pub fn search_logical(&self, connector: &str) -> Option<&LogicalMonitor> {
+        self.logical_monitors
+            .iter()
+            .find(|monitor| monitor.monitors.iter().any(|pm| pm.connector == *connector))
+    }
+
+    /// The largest screen the display server can drive, if any monitor reports a limit. The limits
+    /// can differ per axis between monitors, so this is the smallest one on each axis.
+    pub fn max_screen_size(&self) -> Option<(i32, i32)> {
+        self.monitors
+            .iter()
+            .filter_map(|monitor| monitor.known_properties.max_screen_size)
+            .reduce(|(width, height), (max_width, max_height)| {
+                (width.min(max_width), height.min(max_height))
+            })
+    }
+
+    pub fn search(&self, connector: &str) -> Option<(&LogicalMonitor, &PhysicalMonitor)> {
+        let physical_monitor = self.search_physical(connector);
+
+        let logical_monitor = self.search_logical(connector);
 
         physical_monitor
             .map(|physical_monitor| {
@@ -170,12 +207,7 @@ impl DisplayConfig {
         if !summary {
             // Print known and unknown properties.
             write!(writer, "{}", self.known_properties)?;
-            // TODO: this should be sorted
-            for (prop, value) in self.properties.iter() {
-                if !KNOWN_PROPERTY_KEYS.contains(&prop.as_str()) {
-                    writeln!(writer, "{}: {:?}", prop, &value.0)?;
-                }
-            }
+            properties::format_properties(writer, &self.properties)?;
             writeln!(writer)?;
         }
 
@@ -1,3 +1,7 @@
+use std::cmp::Ordering;
+
+use super::properties;
+
 #[derive(Debug)]
 pub struct KnownModeProperties {
     pub is_current: bool,
@@ -6,15 +10,8 @@ pub struct KnownModeProperties {
 
 const KNOWN_MODE_PROPERTY_KEYS: [&str; 2] = ["is-current", "is-preferred"];
 impl KnownModeProperties {
-    fn from(properties: &dbus::arg::PropMap) -> KnownModeProperties {
-        // TODO: move into a helper
-        let as_bool = |key: &str| -> Option<bool> {
-            match properties.get(key).map(|val| val.0.as_u64()).flatten() {
-                Some(1) => Some(true),
-                Some(0) => Some(false),
-                _ => None,
-            }
-        };
+    fn from(result: &dbus::arg::PropMap) -> KnownModeProperties {
+        let as_bool = |key: &str| properties::get_bool(result, key);
 
         KnownModeProperties {
             is_current: as_bool("is-current").unwrap_or(false),
@@ -43,6 +40,10 @@ pub struct Mode {
 }
 
 impl Mode {
+    pub fn aspect_ratio(&self) -> AspectRatio {
+        AspectRatio::from(self.width, self.height)
+    }
+
     pub fn from(result: (String, i32, i32, f64, f64, Vec<f64>, dbus::arg::PropMap)) -> Mode {
         let all_properties = result.6;
         let known_properties = KnownModeProperties::from(&all_properties);
@@ -61,6 +62,34 @@ impl Mode {
                 .collect(),
         }
     }
+
+    /// The supported scale matching the given one within `SCALE_TOLERANCE`. The display server
+    /// expects the exact value it advertised (e.g. 1.7475 rather than 1.75), so use the result.
+    pub fn find_scale(&self, scale: f64) -> Option<f64> {
+        self.supported_scales
+            .iter()
+            .find(|supported| (*supported - scale).abs() <= SCALE_TOLERANCE)
+            .copied()
+    }
+
+    /// The supported scale closest to the given one.
+    pub fn nearest_scale(&self, scale: f64) -> Option<f64> {
+        self.nearest_common_scale(scale, &[])
+    }
+
+    /// The scale closest to the given one that is supported by this mode and all the `others`,
+    /// e.g. the modes of monitors mirroring each other.
+    pub fn nearest_common_scale(&self, scale: f64, others: &[&Mode]) -> Option<f64> {
+        self.supported_scales
+            .iter()
+            .copied()
+            .filter(|supported| {
+                others
+                    .iter()
+                    .all(|mode| mode.find_scale(*supported).is_some())
+            })
+            .min_by(|a, b| (a - scale).abs().total_cmp(&(b - scale).abs()))
+    }
 }
 
 impl std::fmt::Display for Mode {
@@ -111,6 +140,398 @@ impl std::fmt::Display for Mode {
     }
 }
 
+/// How far (in Hz) a requested refresh rate may be from a mode's actual rate, e.g. "60" matches
+/// 59.951Hz.
+pub const REFRESH_RATE_TOLERANCE: f64 = 1.0;
+
+/// How far a requested scale may be from a supported one, e.g. "1.75" matches 1.7475.
+pub const SCALE_TOLERANCE: f64 = 0.01;
+
+/// A way of picking one of a monitor's modes without knowing its exact ID.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ModeSelector {
+    /// an exact mode ID, as listed by `GetCurrentState`
+    Id(String),
+    /// a resolution such as "2560x1440", optionally with a refresh rate as in "2560x1440@144"
+    Resolution {
+        width: i32,
+        height: i32,
+        refresh_rate: Option<f64>,
+    },
+    /// the mode the monitor reports as preferred
+    Preferred,
+    /// the largest resolution the monitor supports
+    Native,
+    /// the highest refresh rate available at the current (or preferred) resolution
+    HighestRefresh,
+}
+
+impl std::str::FromStr for ModeSelector {
+    type Err = std::convert::Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "preferred" => return Ok(ModeSelector::Preferred),
+            "native" => return Ok(ModeSelector::Native),
+            "highest-refresh" => return Ok(ModeSelector::HighestRefresh),
+            _ => {}
+        }
+
+        let (resolution, refresh_rate) = match s.split_once('@') {
+            Some((resolution, refresh_rate)) => (resolution, Some(refresh_rate)),
+            None => (s, None),
+        };
+
+        let parsed = resolution.split_once('x').and_then(|(width, height)| {
+            Some(ModeSelector::Resolution {
+                width: width.parse().ok()?,
+                height: height.parse().ok()?,
+                refresh_rate: match refresh_rate {
+                    Some(refresh_rate) => Some(refresh_rate.parse().ok()?),
+                    None => None,
+                },
+            })
+        });
+
+        Ok(parsed.unwrap_or_else(|| ModeSelector::Id(s.to_string())))
+    }
+}
+
+impl std::fmt::Display for ModeSelector {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ModeSelector::Id(id) => write!(f, "{}", id),
+            ModeSelector::Resolution {
+                width,
+                height,
+                refresh_rate: Some(refresh_rate),
+            } => write!(f, "{}x{}@{}", width, height, refresh_rate),
+            ModeSelector::Resolution { width, height, .. } => write!(f, "{}x{}", width, height),
+            ModeSelector::Preferred => write!(f, "preferred"),
+            ModeSelector::Native => write!(f, "native"),
+            ModeSelector::HighestRefresh => write!(f, "highest-refresh"),
+        }
+    }
+}
+
+const MM_PER_INCH: f64 = 25.4;
+
+// EDID allows storing the aspect ratio instead of the size, which some monitors (and most
+// projectors) do. These are the values that encoding produces, as recognised by mutter.
+const ASPECT_RATIO_SIZES: [(i32, i32); 6] = [
+    (1600, 900),
+    (1600, 1000),
+    (160, 90),
+    (160, 100),
+    (16, 9),
+    (16, 10),
+];
+
+// No real monitor is smaller than this in either dimension.
+const MIN_PLAUSIBLE_SIZE_MM: i32 = 20;
+
+/// The physical size a monitor reports, if any.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PhysicalSize {
+    Reported {
+        width_mm: i32,
+        height_mm: i32,
+    },
+    /// The monitor doesn't report a size, or reports it as 0x0.
+    Missing,
+    /// The monitor reports a size that can't be real, usually its aspect ratio.
+    Bogus {
+        width_mm: i32,
+        height_mm: i32,
+    },
+}
+
+impl std::fmt::Display for PhysicalSize {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PhysicalSize::Reported {
+                width_mm,
+                height_mm,
+            } => write!(f, "{}mm x {}mm", width_mm, height_mm),
+            PhysicalSize::Missing => write!(f, "unknown"),
+            PhysicalSize::Bogus {
+                width_mm,
+                height_mm,
+            } => write!(
+                f,
+                "unknown (reports {}mm x {}mm, which is not a real size)",
+                width_mm, height_mm
+            ),
+        }
+    }
+}
+
+/// The aspect ratio of a mode, e.g. 16:9.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AspectRatio {
+    pub width: i32,
+    pub height: i32,
+}
+
+// Resolutions are often slightly off their nominal ratio (1366x768 is "16:9"), so these are
+// matched within ASPECT_RATIO_TOLERANCE before falling back to the reduced fraction.
+const COMMON_ASPECT_RATIOS: [(i32, i32); 8] = [
+    (16, 9),
+    (16, 10),
+    (4, 3),
+    (5, 4),
+    (3, 2),
+    (21, 9),
+    (32, 9),
+    (1, 1),
+];
+const ASPECT_RATIO_TOLERANCE: f64 = 0.03;
+
+impl AspectRatio {
+    pub fn from(width: i32, height: i32) -> AspectRatio {
+        let ratio = width as f64 / height as f64;
+        if let Some((width, height)) = COMMON_ASPECT_RATIOS.iter().find(|(width, height)| {
+            let common = *width as f64 / *height as f64;
+            ((ratio - common) / common).abs() <= ASPECT_RATIO_TOLERANCE
+        }) {
+            return AspectRatio {
+                width: *width,
+                height: *height,
+            };
+        }
+
+        fn gcd(a: i32, b: i32) -> i32 {
+            if b == 0 {
+                a
+            } else {
+                gcd(b, a % b)
+            }
+        }
+
+        let divisor = gcd(width, height).max(1);
+        AspectRatio {
+            width: width / divisor,
+            height: height / divisor,
+        }
+    }
+}
+
+impl std::fmt::Display for AspectRatio {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}", self.width, self.height)
+    }
+}
+
+/// A rough classification of pixel density, by DPI.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DensityClass {
+    /// Below 100 DPI, e.g. large 1080p monitors and TVs.
+    Low,
+    /// 100 to 150 DPI, e.g. 27" 1440p monitors.
+    Standard,
+    /// 150 to 250 DPI, e.g. 27" 4K monitors and most HiDPI laptops.
+    High,
+    /// 250 DPI and above, e.g. small 4K laptop panels.
+    VeryHigh,
+}
+
+impl DensityClass {
+    pub fn from(dpi: f64) -> DensityClass {
+        if dpi < 100.0 {
+            DensityClass::Low
+        } else if dpi < 150.0 {
+            DensityClass::Standard
+        } else if dpi < 250.0 {
+            DensityClass::High
+        } else {
+            DensityClass::VeryHigh
+        }
+    }
+}
+
+impl std::fmt::Display for DensityClass {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                DensityClass::Low => "low",
+                DensityClass::Standard => "standard",
+                DensityClass::High => "high",
+                DensityClass::VeryHigh => "very high",
+            }
+        )
+    }
+}
+
+/// The state of a privacy screen, which narrows the viewing angle of a laptop panel.
+#[derive(Debug, Clone, Copy)]
+pub struct PrivacyScreen {
+    pub enabled: bool,
+    // true if the state can only be changed in hardware, e.g. by a key combination
+    pub locked: bool,
+}
+
+const KNOWN_MONITOR_PROPERTY_KEYS: [&str; 9] = [
+    "width-mm",
+    "height-mm",
+    "is-underscanning",
+    "max-screen-size",
+    "is-builtin",
+    "display-name",
+    "min-refresh-rate",
+    "privacy-screen-state",
+    "is-for-lease",
+];
+
+#[derive(Debug)]
+pub struct KnownMonitorProperties {
+    // physical width of monitor in millimeters
+    pub width_mm: Option<i32>,
+    // physical height of monitor in millimeters
+    pub height_mm: Option<i32>,
+    // whether underscanning is enabled (absence of this means underscanning not being supported)
+    pub is_underscanning: Option<bool>,
+    // the maximum size a screen may have (absence of this means unlimited screen size)
+    pub max_screen_size: Option<(i32, i32)>,
+    // whether the monitor is built in, e.g. a laptop panel
+    pub is_builtin: bool,
+    // a human readable display name of the monitor
+    pub display_name: Option<String>,
+    // the minimum refresh rate of a monitor with a variable refresh rate
+    pub min_refresh_rate: Option<i32>,
+    // absence of this means the monitor has no privacy screen
+    pub privacy_screen: Option<PrivacyScreen>,
+    // whether the monitor is leased to another client (e.g. a VR headset) rather than the desktop
+    pub is_for_lease: bool,
+}
+
+impl KnownMonitorProperties {
+    fn from(result: &dbus::arg::PropMap) -> KnownMonitorProperties {
+        let as_bool = |key: &str| properties::get_bool(result, key);
+
+        let pair = |key: &str| {
+            let tuple = properties::get_tuple(result, key)?;
+            match tuple.as_slice() {
+                [first, second] => Some((*first, *second)),
+                _ => None,
+            }
+        };
+
+        KnownMonitorProperties {
+            width_mm: properties::get_i32(result, "width-mm"),
+            height_mm: properties::get_i32(result, "height-mm"),
+            is_underscanning: as_bool("is-underscanning"),
+            max_screen_size: pair("max-screen-size").and_then(|(width, height)| {
+                Some((width.as_i64()? as i32, height.as_i64()? as i32))
+            }),
+            is_builtin: as_bool("is-builtin").unwrap_or(false),
+            display_name: properties::get_string(result, "display-name"),
+            min_refresh_rate: properties::get_i32(result, "min-refresh-rate"),
+            privacy_screen: pair("privacy-screen-state").and_then(|(enabled, locked)| {
+                Some(PrivacyScreen {
+                    enabled: enabled.as_u64()? == 1,
+                    locked: locked.as_u64()? == 1,
+                })
+            }),
+            is_for_lease: as_bool("is-for-lease").unwrap_or(false),
+        }
+    }
+
+    /// The reported physical size, checked for the values monitors report when they don't know it.
+    pub fn physical_size(&self) -> PhysicalSize {
+        let (width_mm, height_mm) = match (self.width_mm, self.height_mm) {
+            (Some(width_mm), Some(height_mm)) if width_mm > 0 && height_mm > 0 => {
+                (width_mm, height_mm)
+            }
+            _ => return PhysicalSize::Missing,
+        };
+
+        if ASPECT_RATIO_SIZES.contains(&(width_mm, height_mm))
+            || width_mm < MIN_PLAUSIBLE_SIZE_MM
+            || height_mm < MIN_PLAUSIBLE_SIZE_MM
+        {
+            PhysicalSize::Bogus {
+                width_mm,
+                height_mm,
+            }
+        } else {
+            PhysicalSize::Reported {
+                width_mm,
+                height_mm,
+            }
+        }
+    }
+
+    /// The diagonal of the monitor in inches, if it reports a plausible size.
+    pub fn diagonal_inches(&self) -> Option<f64> {
+        match self.physical_size() {
+            PhysicalSize::Reported {
+                width_mm,
+                height_mm,
+            } => Some((width_mm as f64).hypot(height_mm as f64) / MM_PER_INCH),
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Display for KnownMonitorProperties {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        fn on_off(val: bool) -> &'static str {
+            if val {
+                "on"
+            } else {
+                "off"
+            }
+        }
+
+        if let Some(display_name) = &self.display_name {
+            writeln!(f, "display-name: {}", display_name)?;
+        }
+        writeln!(
+            f,
+            "type: {}",
+            if self.is_builtin {
+                "built-in"
+            } else {
+                "external"
+            }
+        )?;
+        match self.physical_size() {
+            PhysicalSize::Missing => {}
+            size => match self.diagonal_inches() {
+                Some(diagonal) => writeln!(f, "size: {} ({:.1}in)", size, diagonal)?,
+                None => writeln!(f, "size: {}", size)?,
+            },
+        }
+        if let Some(is_underscanning) = self.is_underscanning {
+            writeln!(f, "underscanning: {}", on_off(is_underscanning))?;
+        }
+        if let Some((width, height)) = self.max_screen_size {
+            writeln!(f, "max-screen-size: {}x{}", width, height)?;
+        }
+        if let Some(min_refresh_rate) = self.min_refresh_rate {
+            writeln!(f, "min-refresh-rate: {}Hz", min_refresh_rate)?;
+        }
+        if let Some(privacy_screen) = self.privacy_screen {
+            writeln!(
+                f,
+                "privacy-screen: {}{}",
+                on_off(privacy_screen.enabled),
+                if privacy_screen.locked {
+                    " (locked)"
+                } else {
+                    ""
+                }
+            )?;
+        }
+        if self.is_for_lease {
+            writeln!(f, "leased: yes")?;
+        }
+        Ok(())
+    }
+}
+
 /// represent connected physical monitors
 #[derive(Debug)]
 pub struct PhysicalMonitor {
@@ -125,24 +546,8 @@ pub struct PhysicalMonitor {
     // available modes
     pub modes: Vec<Mode>,
 
-    /**
-     * optional properties, including:
-     *   - "width-mm" (i): physical width of monitor in millimeters
-     *   - "height-mm" (i): physical height of monitor in millimeters
-     *   - "is-underscanning" (b): whether underscanning is enabled
-     *                   (absence of this means underscanning
-     *                   not being supported)
-     *   - "max-screen-size" (ii): the maximum size a screen may have
-     *                   (absence of this means unlimited screen
-     *                   size)
-     *   - "is-builtin" (b): whether the monitor is built in, e.g. a
-     *           laptop panel (absence of this means it is
-     *           not built in)
-     *   - "display-name" (s): a human readable display name of the monitor
-     *   Possible mode flags:
-     *   1 : preferred mode
-     *   2 : current mode
-     */
+    pub known_properties: KnownMonitorProperties,
+    // properties not covered by `known_properties`
     pub properties: dbus::arg::PropMap,
 }
 
@@ -160,9 +565,117 @@ impl PhysicalMonitor {
             product: result.0 .2,
             serial: result.0 .3,
             modes: result.1.into_iter().map(Mode::from).collect(),
-            properties: result.2,
+            known_properties: KnownMonitorProperties::from(&result.2),
+            properties: result
+                .2
+                .into_iter()
+                .filter(|(key, _)| !KNOWN_MONITOR_PROPERTY_KEYS.contains(&key.as_str()))
+                .collect(),
         }
     }
+
+    /// Finds a mode by resolution. Without a refresh rate this prefers the current mode, then the
+    /// preferred mode, then the highest refresh rate; with one it picks the closest rate within
+    /// `REFRESH_RATE_TOLERANCE`.
+    pub fn find_mode(&self, width: i32, height: i32, refresh_rate: Option<f64>) -> Option<&Mode> {
+        let candidates = self
+            .modes
+            .iter()
+            .filter(|mode| mode.width == width && mode.height == height);
+
+        match refresh_rate {
+            Some(refresh_rate) => candidates
+                .map(|mode| (mode, (mode.refresh_rate - refresh_rate).abs()))
+                .filter(|(_, distance)| *distance <= REFRESH_RATE_TOLERANCE)
+                .min_by(|(_, a), (_, b)| a.partial_cmp(b).unwrap_or(Ordering::Equal))
+                .map(|(mode, _)| mode),
+            None => candidates.max_by(|a, b| {
+                (
+                    a.known_properties.is_current,
+                    a.known_properties.is_preferred,
+                )
+                    .cmp(&(
+                        b.known_properties.is_current,
+                        b.known_properties.is_preferred,
+                    ))
+                    .then(
+                        a.refresh_rate
+                            .partial_cmp(&b.refresh_rate)
+                            .unwrap_or(Ordering::Equal),
+                    )
+            }),
+        }
+    }
+
+    pub fn select_mode(&self, selector: &ModeSelector) -> Option<&Mode> {
+        match selector {
+            ModeSelector::Id(id) => self.modes.iter().find(|mode| mode.id == *id),
+            ModeSelector::Resolution {
+                width,
+                height,
+                refresh_rate,
+            } => self.find_mode(*width, *height, *refresh_rate),
+            ModeSelector::Preferred => self.preferred_mode(),
+            ModeSelector::Native => self
+                .modes
+                .iter()
+                .max_by_key(|mode| (mode.width * mode.height, mode.width))
+                .and_then(|mode| self.find_mode(mode.width, mode.height, None)),
+            ModeSelector::HighestRefresh => {
+                let base = self.current_mode().or_else(|| self.preferred_mode())?;
+                self.modes
+                    .iter()
+                    .filter(|mode| mode.width == base.width && mode.height == base.height)
+                    .max_by(|a, b| {
+                        a.refresh_rate
+                            .partial_cmp(&b.refresh_rate)
+                            .unwrap_or(Ordering::Equal)
+                    })
+            }
+        }
+    }
+
+    /// The monitor's modes grouped by resolution, in the order they were listed.
+    pub fn resolutions(&self) -> Vec<((i32, i32), Vec<&Mode>)> {
+        let mut resolutions: Vec<((i32, i32), Vec<&Mode>)> = Vec::new();
+        for mode in self.modes.iter() {
+            let resolution = (mode.width, mode.height);
+            match resolutions
+                .iter_mut()
+                .find(|(other, _)| *other == resolution)
+            {
+                Some((_, modes)) => modes.push(mode),
+                None => resolutions.push((resolution, vec![mode])),
+            }
+        }
+        resolutions
+    }
+
+    /// The physical pixel density of the given mode, if the monitor reports a plausible size.
+    pub fn dpi(&self, mode: &Mode) -> Option<f64> {
+        let diagonal_inches = self.known_properties.diagonal_inches()?;
+        Some((mode.width as f64).hypot(mode.height as f64) / diagonal_inches)
+    }
+
+    /// The pixel density of the given mode once scaled, i.e. how large things appear compared to
+    /// an unscaled monitor of that density.
+    pub fn effective_dpi(&self, mode: &Mode, scale: f64) -> Option<f64> {
+        Some(self.dpi(mode)? / scale)
+    }
+
+    pub fn current_mode(&self) -> Option<&Mode> {
+        self.modes
+            .iter()
+            .find(|mode| mode.known_properties.is_current)
+    }
+
+    /// The mode flagged as preferred by the monitor, falling back to the first listed mode.
+    pub fn preferred_mode(&self) -> Option<&Mode> {
+        self.modes
+            .iter()
+            .find(|mode| mode.known_properties.is_preferred)
+            .or_else(|| self.modes.first())
+    }
 }
 
 impl std::fmt::Display for PhysicalMonitor {
@@ -177,10 +690,110 @@ impl std::fmt::Display for PhysicalMonitor {
             writeln!(f, "{}", &mode)?;
         }
 
-        // TODO: improve logging of properties
-        for (prop, value) in self.properties.iter() {
-            writeln!(f, "{}: {:?}", prop, &value.0)?;
+        write!(f, "{}", self.known_properties)?;
+        properties::format_properties(f, &self.properties)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::display_config::testing;
+
+    #[test]
+    fn parses_mode_selectors() {
+        let parse = |s: &str| s.parse::<ModeSelector>().unwrap();
+
+        assert_eq!(parse("preferred"), ModeSelector::Preferred);
+        assert_eq!(parse("Native"), ModeSelector::Native);
+        assert_eq!(parse("highest-refresh"), ModeSelector::HighestRefresh);
+        assert_eq!(
+            parse("2560x1440"),
+            ModeSelector::Resolution {
+                width: 2560,
+                height: 1440,
+                refresh_rate: None
+            }
+        );
+        assert_eq!(
+            parse("2560x1440@144"),
+            ModeSelector::Resolution {
+                width: 2560,
+                height: 1440,
+                refresh_rate: Some(144.0)
+            }
+        );
+        // Anything else is taken to be a mode ID.
+        assert_eq!(
+            parse("2560x1440@fast"),
+            ModeSelector::Id("2560x1440@fast".to_string())
+        );
+    }
+
+    #[test]
+    fn mode_selectors_round_trip() {
+        for s in ["preferred", "native", "2560x1440", "2560x1440@59.951"] {
+            assert_eq!(s.parse::<ModeSelector>().unwrap().to_string(), s);
         }
-        Ok(())
+    }
+
+    #[test]
+    fn finds_modes_by_resolution() {
+        let config = testing::laptop_and_monitor();
+        let monitor = config.search_physical("DP-1").unwrap();
+        let find = |width, height, refresh_rate| {
+            monitor
+                .find_mode(width, height, refresh_rate)
+                .map(|mode| mode.id.as_str())
+        };
+
+        // Without a rate, the current mode wins over a higher refresh rate.
+        assert_eq!(find(2560, 1440, None), Some("2560x1440@59.951"));
+        // With one, the closest rate within the tolerance.
+        assert_eq!(find(2560, 1440, Some(60.0)), Some("2560x1440@59.951"));
+        assert_eq!(find(2560, 1440, Some(144.0)), Some("2560x1440@143.912"));
+        assert_eq!(find(2560, 1440, Some(120.0)), None);
+        assert_eq!(find(3840, 2160, None), None);
+    }
+
+    #[test]
+    fn selects_modes() {
+        let config = testing::laptop_and_monitor();
+        let monitor = config.search_physical("DP-1").unwrap();
+        let select =
+            |selector: ModeSelector| monitor.select_mode(&selector).map(|mode| mode.id.as_str());
+
+        assert_eq!(select(ModeSelector::Preferred), Some("2560x1440@59.951"));
+        assert_eq!(select(ModeSelector::Native), Some("2560x1440@59.951"));
+        assert_eq!(
+            select(ModeSelector::HighestRefresh),
+            Some("2560x1440@143.912")
+        );
+        assert_eq!(
+            select(ModeSelector::Id("1920x1080@60.000".to_string())),
+            Some("1920x1080@60.000")
+        );
+        assert_eq!(select(ModeSelector::Id("nope".to_string())), None);
+
+        // A disabled monitor has no current mode, so the preferred one is the base.
+        let projector = config.search_physical("HDMI-1").unwrap();
+        assert_eq!(
+            projector
+                .select_mode(&ModeSelector::HighestRefresh)
+                .map(|mode| mode.id.as_str()),
+            Some("1920x1080@60.000")
+        );
+    }
+
+    #[test]
+    fn finds_the_nearest_common_scale() {
+        let config = testing::laptop_and_monitor();
+        let laptop = &config.search_physical("eDP-1").unwrap().modes[0];
+        let monitor = &config.search_physical("DP-1").unwrap().modes[2];
+
+        assert_eq!(laptop.nearest_scale(1.7), Some(1.75));
+        // 1920x1080 on the monitor doesn't support 1.5 or 1.75.
+        assert_eq!(laptop.nearest_common_scale(1.7, &[monitor]), Some(2.0));
+        assert_eq!(laptop.nearest_common_scale(1.3, &[monitor]), Some(1.25));
     }
 }
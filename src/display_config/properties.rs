@@ -0,0 +1,86 @@
+use dbus::arg::{ArgType, PropMap, RefArg};
+
+pub(crate) fn get_bool(properties: &PropMap, key: &str) -> Option<bool> {
+    match properties.get(key).and_then(|val| val.0.as_u64()) {
+        Some(1) => Some(true),
+        Some(0) => Some(false),
+        _ => None,
+    }
+}
+
+pub(crate) fn get_i32(properties: &PropMap, key: &str) -> Option<i32> {
+    properties
+        .get(key)
+        .and_then(|val| val.0.as_i64())
+        .map(|val| val as i32)
+}
+
+pub(crate) fn get_string(properties: &PropMap, key: &str) -> Option<String> {
+    properties
+        .get(key)
+        .and_then(|val| val.0.as_str())
+        .map(String::from)
+}
+
+/// The members of a struct (or array) property, e.g. the `(ii)` of "max-screen-size".
+pub(crate) fn get_tuple<'a>(properties: &'a PropMap, key: &str) -> Option<Vec<&'a dyn RefArg>> {
+    Some(properties.get(key)?.0.as_iter()?.collect())
+}
+
+/// Formats a property value for people rather than as its `Debug` representation: strings are
+/// printed as-is, structs as `(a, b)`, arrays as `[a, b]` and dicts as `{key: value}`.
+pub(crate) fn format_value(value: &dyn RefArg) -> String {
+    match value.arg_type() {
+        ArgType::Boolean => (value.as_u64() == Some(1)).to_string(),
+        ArgType::Double => value.as_f64().map_or(String::new(), |val| val.to_string()),
+        ArgType::Byte | ArgType::UInt16 | ArgType::UInt32 | ArgType::UInt64 | ArgType::UnixFd => {
+            value.as_u64().map_or(String::new(), |val| val.to_string())
+        }
+        ArgType::Int16 | ArgType::Int32 | ArgType::Int64 => {
+            value.as_i64().map_or(String::new(), |val| val.to_string())
+        }
+        ArgType::String | ArgType::ObjectPath | ArgType::Signature => {
+            value.as_str().unwrap_or_default().to_string()
+        }
+        ArgType::Variant => value
+            .as_iter()
+            .and_then(|mut inner| inner.next().map(format_value))
+            .unwrap_or_default(),
+        ArgType::Array if value.signature().starts_with("a{") => {
+            let mut entries = Vec::new();
+            if let Some(mut items) = value.as_iter() {
+                while let (Some(key), Some(entry)) = (items.next(), items.next()) {
+                    entries.push(format!("{}: {}", format_value(key), format_value(entry)));
+                }
+            }
+            entries.sort();
+            format!("{{{}}}", entries.join(", "))
+        }
+        ArgType::Array | ArgType::Struct | ArgType::DictEntry => {
+            let items: Vec<String> = value
+                .as_iter()
+                .map(|items| items.map(format_value).collect())
+                .unwrap_or_default();
+            if value.arg_type() == ArgType::Array {
+                format!("[{}]", items.join(", "))
+            } else {
+                format!("({})", items.join(", "))
+            }
+        }
+        ArgType::Invalid => String::new(),
+    }
+}
+
+/// Writes each property on its own line, sorted by key.
+pub(crate) fn format_properties(
+    writer: &mut dyn std::fmt::Write,
+    properties: &PropMap,
+) -> std::fmt::Result {
+    let mut keys: Vec<&String> = properties.keys().collect();
+    keys.sort();
+
+    for key in keys {
+        writeln!(writer, "{}: {}", key, format_value(&properties[key].0))?;
+    }
+    Ok(())
+}
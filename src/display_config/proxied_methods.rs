@@ -1,9 +1,16 @@
 use dbus::{
-    arg::PropMap,
+    arg::{PropMap, Variant},
     blocking::{Connection, Proxy},
+    channel::Token,
+    Message,
 };
 
-use super::{logical_monitor::LogicalMonitor, physical_monitor::PhysicalMonitor, DisplayConfig};
+use super::{
+    geometry::{self, Rect},
+    logical_monitor::{LogicalMonitor, Transform},
+    physical_monitor::PhysicalMonitor,
+    DisplayConfig, LayoutMode,
+};
 
 type Result<T> = std::prelude::rust_2021::Result<T, dbus::Error>;
 
@@ -30,9 +37,11 @@ pub struct ApplyConfig<'a> {
 }
 
 impl ApplyConfig<'_> {
+    /// Builds the config for an existing logical monitor, keeping every physical monitor that
+    /// displays it (more than one when mirroring) on its current mode.
     pub fn from<'a>(
         logical_monitor: &LogicalMonitor,
-        physical_monitor: &'a PhysicalMonitor,
+        physical_monitors: &'a [PhysicalMonitor],
     ) -> ApplyConfig<'a> {
         ApplyConfig {
             x_pos: logical_monitor.x,
@@ -40,18 +49,62 @@ impl ApplyConfig<'_> {
             scale: logical_monitor.scale,
             transform: logical_monitor.transform.bits(),
             primary: logical_monitor.primary,
-            monitors: vec![ApplyMonitor {
-                connector: &physical_monitor.connector,
-                mode_id: &physical_monitor
-                    .modes
-                    .iter()
-                    .find(|mode| mode.known_properties.is_current)
-                    .unwrap()
-                    .id,
-            }],
+            monitors: logical_monitor
+                .monitors
+                .iter()
+                .filter_map(|monitor| {
+                    physical_monitors
+                        .iter()
+                        .find(|physical_monitor| physical_monitor.connector == monitor.connector)
+                })
+                .filter_map(|physical_monitor| {
+                    Some(ApplyMonitor {
+                        connector: &physical_monitor.connector,
+                        mode_id: &physical_monitor.current_mode()?.id,
+                    })
+                })
+                .collect(),
         }
     }
 
+    /// The size the logical monitor would take up in the global screen space, based on the mode of
+    /// its first monitor found in `physical_monitors`.
+    pub fn logical_size(
+        &self,
+        physical_monitors: &[PhysicalMonitor],
+        layout_mode: LayoutMode,
+    ) -> Option<(i32, i32)> {
+        let mode = self.monitors.iter().find_map(|monitor| {
+            physical_monitors
+                .iter()
+                .find(|physical_monitor| physical_monitor.connector == monitor.connector)?
+                .modes
+                .iter()
+                .find(|mode| mode.id == monitor.mode_id)
+        })?;
+
+        Some(geometry::logical_size(
+            mode,
+            self.scale,
+            Transform::from_bits_truncate(self.transform),
+            layout_mode,
+        ))
+    }
+
+    pub fn rect(
+        &self,
+        physical_monitors: &[PhysicalMonitor],
+        layout_mode: LayoutMode,
+    ) -> Option<Rect> {
+        let (width, height) = self.logical_size(physical_monitors, layout_mode)?;
+        Some(Rect {
+            x: self.x_pos,
+            y: self.y_pos,
+            width,
+            height,
+        })
+    }
+
     pub fn serialize(&self) -> (i32, i32, f64, u32, bool, Vec<(&str, &str, PropMap)>) {
         (
             self.x_pos,
@@ -68,25 +121,54 @@ impl ApplyConfig<'_> {
 }
 
 impl DisplayConfig {
+    /// Applies the given logical monitors, replacing the current configuration. `layout_mode` is
+    /// only sent when it is set, so that compositors that can't change it don't reject the call.
     pub fn apply_monitors_config(
         &self,
         proxy: &Proxy<&Connection>,
         configs: Vec<ApplyConfig>,
+        layout_mode: Option<LayoutMode>,
         persistent: bool,
+    ) -> Result<()> {
+        self.send_monitors_config(proxy, &configs, layout_mode, if persistent { 2 } else { 1 })
+    }
+
+    /// Asks the display server whether it would accept the given logical monitors, without applying
+    /// them. The error it returns explains why a configuration would be rejected.
+    pub fn verify_monitors_config(
+        &self,
+        proxy: &Proxy<&Connection>,
+        configs: Vec<ApplyConfig>,
+        layout_mode: Option<LayoutMode>,
+    ) -> Result<()> {
+        self.send_monitors_config(proxy, &configs, layout_mode, 0)
+    }
+
+    /// Calls ApplyMonitorsConfig with the given method: 0 verifies the configuration, 1 applies it
+    /// temporarily and 2 applies it persistently.
+    fn send_monitors_config(
+        &self,
+        proxy: &Proxy<&Connection>,
+        configs: &[ApplyConfig],
+        layout_mode: Option<LayoutMode>,
+        method: u32,
     ) -> Result<()> {
         use super::raw::OrgGnomeMutterDisplayConfig;
 
-        let result = proxy.apply_monitors_config(
+        let mut properties = PropMap::new();
+        if let Some(layout_mode) = layout_mode {
+            properties.insert(
+                "layout-mode".to_string(),
+                Variant(Box::new(layout_mode.bits())),
+            );
+        }
+
+        proxy.apply_monitors_config(
             self.serial,
-            if persistent { 2 } else { 1 },
+            method,
             configs.iter().map(|config| config.serialize()).collect(),
-            PropMap::new(),
-        );
-
-        if let Err(err) = &result {
-            println!("{:?}", err);
-        }
-        result
+            properties,
+        )
     }
 
     pub fn get_current_state(proxy: &Proxy<&Connection>) -> Result<DisplayConfig> {
@@ -95,4 +177,20 @@ impl DisplayConfig {
         let raw_output = proxy.get_current_state()?;
         Ok(DisplayConfig::from(raw_output))
     }
+
+    /// Calls `callback` whenever the monitor configuration changes, e.g. a monitor is plugged in or
+    /// a new configuration is applied. The callback only fires while the connection is processing
+    /// messages, and stays registered for as long as it returns `true`.
+    pub fn on_monitors_changed<F>(proxy: &Proxy<&Connection>, mut callback: F) -> Result<Token>
+    where
+        F: FnMut() -> bool + Send + 'static,
+    {
+        use super::raw::OrgGnomeMutterDisplayConfigMonitorsChanged;
+
+        proxy.match_signal(
+            move |_: OrgGnomeMutterDisplayConfigMonitorsChanged, _: &Connection, _: &Message| {
+                callback()
+            },
+        )
+    }
 }
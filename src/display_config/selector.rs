@@ -0,0 +1,398 @@
+use super::{physical_monitor::PhysicalMonitor, DisplayConfig};
+
+// How many near misses to suggest when nothing matches.
+const MAX_SUGGESTIONS: usize = 3;
+
+/// A way of picking physical monitors that survives them moving to a different connector, e.g.
+/// `vendor:DEL` or `builtin`. Anything that isn't a keyword or prefixed is a connector name, which may
+/// be a glob such as `DP-*`. The values of prefixed selectors may be globs too.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Selector {
+    Connector(String),
+    Vendor(String),
+    Serial(String),
+    Name(String),
+    Builtin,
+    External,
+    Primary,
+    Logical(usize),
+}
+
+#[derive(Debug)]
+pub enum Error {
+    Invalid(String),
+    NotFound {
+        selector: String,
+        suggestions: Vec<String>,
+    },
+    Ambiguous {
+        selector: String,
+        matches: Vec<String>,
+    },
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Invalid(selector) => write!(
+                f,
+                "'{}' is not a valid selector, logical:N takes the index of a logical monitor.",
+                selector
+            ),
+            Error::NotFound {
+                selector,
+                suggestions,
+            } => {
+                write!(f, "unable to find output matching '{}'.", selector)?;
+                if !suggestions.is_empty() {
+                    write!(f, "\ndid you mean {}?", suggestions.join(", "))?;
+                }
+                Ok(())
+            }
+            Error::Ambiguous { selector, matches } => write!(
+                f,
+                "'{}' matches more than one output: {}.",
+                selector,
+                matches.join(", ")
+            ),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl std::str::FromStr for Selector {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // Quotes are allowed around values with spaces, in case the shell passes them through.
+        let value = |value: &str| value.trim().trim_matches('"').to_string();
+
+        match s.split_once(':') {
+            Some(("vendor", vendor)) => Ok(Selector::Vendor(value(vendor))),
+            Some(("serial", serial)) => Ok(Selector::Serial(value(serial))),
+            Some(("name", name)) => Ok(Selector::Name(value(name))),
+            Some(("logical", index)) => index
+                .trim()
+                .parse()
+                .map(Selector::Logical)
+                .map_err(|_| Error::Invalid(s.to_string())),
+            _ => Ok(match s {
+                "builtin" => Selector::Builtin,
+                "external" => Selector::External,
+                "primary" => Selector::Primary,
+                _ => Selector::Connector(s.to_string()),
+            }),
+        }
+    }
+}
+
+impl std::fmt::Display for Selector {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Selector::Connector(connector) => write!(f, "{}", connector),
+            Selector::Vendor(vendor) => write!(f, "vendor:{}", vendor),
+            Selector::Serial(serial) => write!(f, "serial:{}", serial),
+            Selector::Name(name) => write!(f, "name:\"{}\"", name),
+            Selector::Builtin => write!(f, "builtin"),
+            Selector::External => write!(f, "external"),
+            Selector::Primary => write!(f, "primary"),
+            Selector::Logical(index) => write!(f, "logical:{}", index),
+        }
+    }
+}
+
+/// Matches `text` against a pattern where `*` matches any run of characters and `?` any single one.
+fn glob_match(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+
+    // Where to resume after the last `*`: its position in the pattern, and in the text.
+    let mut star: Option<(usize, usize)> = None;
+    let (mut p, mut t) = (0, 0);
+    while t < text.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                star = Some((p, t));
+                p += 1;
+            }
+            Some(c) if *c == '?' || *c == text[t] => {
+                p += 1;
+                t += 1;
+            }
+            _ => match star {
+                Some((star_p, star_t)) => {
+                    p = star_p + 1;
+                    t = star_t + 1;
+                    star = Some((star_p, star_t + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+
+    pattern[p..].iter().all(|c| *c == '*')
+}
+
+/// The number of single character edits to turn `a` into `b`.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+
+    for (i, a) in a.chars().enumerate() {
+        let mut previous = row[0];
+        row[0] = i + 1;
+        for (j, b) in b.iter().enumerate() {
+            let substitution = previous + if a == *b { 0 } else { 1 };
+            previous = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(previous + 1);
+        }
+    }
+
+    row[b.len()]
+}
+
+impl Selector {
+    pub fn matches(&self, config: &DisplayConfig, physical_monitor: &PhysicalMonitor) -> bool {
+        let known_properties = &physical_monitor.known_properties;
+        let insensitive =
+            |pattern: &str, text: &str| glob_match(&pattern.to_lowercase(), &text.to_lowercase());
+
+        match self {
+            Selector::Connector(pattern) => glob_match(pattern, &physical_monitor.connector),
+            Selector::Vendor(pattern) => insensitive(pattern, &physical_monitor.vendor),
+            Selector::Serial(pattern) => glob_match(pattern, &physical_monitor.serial),
+            // Display names are made up by the display server, so the product name works too.
+            Selector::Name(pattern) => {
+                known_properties
+                    .display_name
+                    .as_deref()
+                    .is_some_and(|display_name| insensitive(pattern, display_name))
+                    || insensitive(pattern, &physical_monitor.product)
+            }
+            Selector::Builtin => known_properties.is_builtin,
+            Selector::External => !known_properties.is_builtin,
+            Selector::Primary => config
+                .search_logical(&physical_monitor.connector)
+                .is_some_and(|logical_monitor| logical_monitor.primary),
+            Selector::Logical(index) => {
+                config
+                    .logical_monitors
+                    .get(*index)
+                    .is_some_and(|logical_monitor| {
+                        logical_monitor
+                            .monitors
+                            .iter()
+                            .any(|monitor| monitor.connector == physical_monitor.connector)
+                    })
+            }
+        }
+    }
+
+    /// The value this selector was given, the values it compares against and how to make a
+    /// selector from one of them, for suggesting near misses.
+    #[allow(clippy::type_complexity)]
+    fn compared_values<'a>(
+        &'a self,
+        config: &'a DisplayConfig,
+    ) -> Option<(&'a str, Vec<&'a str>, fn(String) -> Selector)> {
+        let (value, fields, selector): (
+            &str,
+            fn(&PhysicalMonitor) -> Vec<&str>,
+            fn(String) -> Selector,
+        ) = match self {
+            Selector::Connector(connector) => (
+                connector,
+                |monitor| vec![&monitor.connector],
+                Selector::Connector,
+            ),
+            Selector::Vendor(vendor) => (vendor, |monitor| vec![&monitor.vendor], Selector::Vendor),
+            Selector::Serial(serial) => (serial, |monitor| vec![&monitor.serial], Selector::Serial),
+            // Names match either the display name or the product name.
+            Selector::Name(name) => (
+                name,
+                |monitor| {
+                    monitor
+                        .known_properties
+                        .display_name
+                        .as_deref()
+                        .into_iter()
+                        .chain(std::iter::once(monitor.product.as_str()))
+                        .collect()
+                },
+                Selector::Name,
+            ),
+            _ => return None,
+        };
+
+        let mut candidates: Vec<&str> = config
+            .monitors
+            .iter()
+            .flat_map(fields)
+            .filter(|candidate| !candidate.is_empty())
+            .collect();
+        candidates.sort_unstable();
+        candidates.dedup();
+
+        Some((value, candidates, selector))
+    }
+
+    /// The selectors closest to this one that would match something.
+    fn suggestions(&self, config: &DisplayConfig) -> Vec<String> {
+        let (value, candidates, selector) = match self.compared_values(config) {
+            Some(compared) => compared,
+            None => return Vec::new(),
+        };
+
+        let value = value.to_lowercase();
+        let mut candidates: Vec<(usize, &str)> = candidates
+            .into_iter()
+            .map(|candidate| (edit_distance(&value, &candidate.to_lowercase()), candidate))
+            .collect();
+        candidates.sort_by_key(|(distance, _)| *distance);
+
+        // Only suggest values that are a typo or two away, but always suggest something.
+        let threshold = (value.chars().count() / 3).max(2);
+        let close = candidates
+            .iter()
+            .take_while(|(distance, _)| *distance <= threshold)
+            .count()
+            .max(1);
+
+        candidates
+            .into_iter()
+            .take(close.min(MAX_SUGGESTIONS))
+            .map(|(_, candidate)| selector(candidate.to_string()).to_string())
+            .collect()
+    }
+}
+
+impl DisplayConfig {
+    /// Every physical monitor matching the selector.
+    pub fn select_all(&self, selector: &Selector) -> Vec<&PhysicalMonitor> {
+        self.monitors
+            .iter()
+            .filter(|physical_monitor| selector.matches(self, physical_monitor))
+            .collect()
+    }
+
+    /// The one physical monitor matching the selector, e.g. `DP-1`, `vendor:DEL` or `builtin`. An
+    /// exact connector name always wins over a glob matching other connectors too.
+    pub fn select(&self, selector: &str) -> Result<&PhysicalMonitor, Error> {
+        if let Some(physical_monitor) = self.search_physical(selector) {
+            return Ok(physical_monitor);
+        }
+
+        let parsed: Selector = selector.parse()?;
+        match self.select_all(&parsed).as_slice() {
+            [physical_monitor] => Ok(physical_monitor),
+            [] => Err(Error::NotFound {
+                selector: selector.to_string(),
+                suggestions: parsed.suggestions(self),
+            }),
+            matches => Err(Error::Ambiguous {
+                selector: selector.to_string(),
+                matches: matches
+                    .iter()
+                    .map(|physical_monitor| physical_monitor.connector.clone())
+                    .collect(),
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::display_config::testing;
+
+    fn suggestions(selector: &str) -> Vec<String> {
+        match testing::laptop_and_monitor().select(selector) {
+            Err(Error::NotFound { suggestions, .. }) => suggestions,
+            _ => panic!("'{}' should not match anything", selector),
+        }
+    }
+
+    #[test]
+    fn matches_globs() {
+        assert!(glob_match("DP-1", "DP-1"));
+        assert!(glob_match("DP-*", "DP-1"));
+        assert!(glob_match("*", ""));
+        assert!(glob_match("*-1", "eDP-1"));
+        assert!(glob_match("?DP-?", "eDP-1"));
+        assert!(glob_match("D*L*Q", "DELL U2720Q"));
+        assert!(!glob_match("DP-*", "eDP-1"));
+        assert!(!glob_match("DP-?", "DP-10"));
+        assert!(!glob_match("DP-1", "DP-10"));
+        assert!(!glob_match("", "DP-1"));
+    }
+
+    #[test]
+    fn counts_edits() {
+        assert_eq!(edit_distance("", ""), 0);
+        assert_eq!(edit_distance("DP-1", "DP-1"), 0);
+        assert_eq!(edit_distance("DP1", "DP-1"), 1);
+        assert_eq!(edit_distance("DP-2", "DP-1"), 1);
+        assert_eq!(edit_distance("HMDI-1", "HDMI-1"), 2);
+        assert_eq!(edit_distance("", "DP-1"), 4);
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+    }
+
+    #[test]
+    fn parses_selectors() {
+        let parse = |s: &str| s.parse::<Selector>().unwrap();
+
+        assert_eq!(parse("DP-*"), Selector::Connector("DP-*".to_string()));
+        assert_eq!(parse("vendor:DEL"), Selector::Vendor("DEL".to_string()));
+        assert_eq!(
+            parse("name:\"Dell 27\""),
+            Selector::Name("Dell 27".to_string())
+        );
+        assert_eq!(parse("builtin"), Selector::Builtin);
+        assert_eq!(parse("logical: 1"), Selector::Logical(1));
+        assert!(matches!(
+            "logical:first".parse::<Selector>(),
+            Err(Error::Invalid(_))
+        ));
+    }
+
+    #[test]
+    fn selects_monitors() {
+        let config = testing::laptop_and_monitor();
+        let select = |selector: &str| {
+            config
+                .select(selector)
+                .map(|physical_monitor| physical_monitor.connector.as_str())
+        };
+
+        assert_eq!(select("DP-1").unwrap(), "DP-1");
+        assert_eq!(select("vendor:del").unwrap(), "DP-1");
+        assert_eq!(select("serial:ABC*").unwrap(), "DP-1");
+        assert_eq!(select("name:projector").unwrap(), "HDMI-1");
+        assert_eq!(select("name:DELL U2720Q").unwrap(), "DP-1");
+        assert_eq!(select("builtin").unwrap(), "eDP-1");
+        assert_eq!(select("primary").unwrap(), "eDP-1");
+        assert_eq!(select("logical:1").unwrap(), "DP-1");
+        assert!(matches!(
+            select("external"),
+            Err(Error::Ambiguous { matches, .. }) if matches == ["DP-1", "HDMI-1"]
+        ));
+    }
+
+    #[test]
+    fn suggests_near_misses() {
+        assert_eq!(suggestions("HMDI-1"), ["HDMI-1"]);
+        assert_eq!(suggestions("DP-2"), ["DP-1", "eDP-1"]);
+        assert_eq!(suggestions("vendor:DELL"), ["vendor:DEL"]);
+        // Product names are suggested as well as display names.
+        assert_eq!(suggestions("name:DELL U2720")[0], "name:\"DELL U2720Q\"");
+        assert_eq!(suggestions("name:Projecter"), ["name:\"Projector\""]);
+    }
+
+    #[test]
+    fn suggests_something_even_for_distant_values() {
+        assert_eq!(suggestions("XYZ-9").len(), 1);
+        // Keywords have nothing to compare against.
+        assert!(suggestions("logical:5").is_empty());
+    }
+}
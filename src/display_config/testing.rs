@@ -0,0 +1,167 @@
+//! Display configs for unit tests: a laptop panel (eDP-1) with an external monitor (DP-1) to its
+//! right, and a projector (HDMI-1) that is connected but disabled. Public so the command line's
+//! tests can use them too, but not part of the API.
+
+use dbus::arg::{PropMap, RefArg, Variant};
+
+use super::DisplayConfig;
+
+type RawMode = (String, i32, i32, f64, f64, Vec<f64>, PropMap);
+
+/// A logical monitor as (x, y, scale, transform, primary, connectors).
+pub type Logical<'a> = (i32, i32, f64, u32, bool, &'a [&'a str]);
+
+fn variant<T: RefArg + 'static>(value: T) -> Variant<Box<dyn RefArg>> {
+    Variant(Box::new(value))
+}
+
+fn mode(
+    id: &str,
+    (width, height, refresh_rate): (i32, i32, f64),
+    preferred_scale: f64,
+    supported_scales: &[f64],
+    is_current: bool,
+    is_preferred: bool,
+) -> RawMode {
+    let mut properties = PropMap::new();
+    properties.insert("is-current".to_string(), variant(is_current));
+    properties.insert("is-preferred".to_string(), variant(is_preferred));
+
+    (
+        id.to_string(),
+        width,
+        height,
+        refresh_rate,
+        preferred_scale,
+        supported_scales.to_vec(),
+        properties,
+    )
+}
+
+fn monitor_properties(display_name: &str, size_mm: (i32, i32), is_builtin: bool) -> PropMap {
+    let mut properties = PropMap::new();
+    properties.insert(
+        "display-name".to_string(),
+        variant(display_name.to_string()),
+    );
+    properties.insert("width-mm".to_string(), variant(size_mm.0));
+    properties.insert("height-mm".to_string(), variant(size_mm.1));
+    properties.insert("is-builtin".to_string(), variant(is_builtin));
+    properties
+}
+
+fn spec(connector: &str) -> (String, String, String, String) {
+    let (vendor, product, serial) = match connector {
+        "eDP-1" => ("BOE", "0x095f", "0x00000000"),
+        "DP-1" => ("DEL", "DELL U2720Q", "ABC123"),
+        _ => ("EPS", "EPSON PJ", "0x01010101"),
+    };
+
+    (
+        connector.to_string(),
+        vendor.to_string(),
+        product.to_string(),
+        serial.to_string(),
+    )
+}
+
+/// The test monitors with the given logical monitors. The current modes are 2880x1800 on eDP-1
+/// and 2560x1440@59.951 on DP-1.
+pub fn config(logical_monitors: &[Logical]) -> DisplayConfig {
+    let monitors = vec![
+        (
+            spec("eDP-1"),
+            vec![
+                mode(
+                    "2880x1800@60.000",
+                    (2880, 1800, 60.0),
+                    2.0,
+                    &[1.0, 1.25, 1.5, 1.75, 2.0],
+                    true,
+                    true,
+                ),
+                mode(
+                    "1920x1200@59.950",
+                    (1920, 1200, 59.95),
+                    1.0,
+                    &[1.0, 1.25, 1.5, 2.0],
+                    false,
+                    false,
+                ),
+            ],
+            monitor_properties("Built-in display", (302, 189), true),
+        ),
+        (
+            spec("DP-1"),
+            vec![
+                mode(
+                    "2560x1440@59.951",
+                    (2560, 1440, 59.951),
+                    1.0,
+                    &[1.0, 1.25, 1.5, 2.0],
+                    true,
+                    true,
+                ),
+                mode(
+                    "2560x1440@143.912",
+                    (2560, 1440, 143.912),
+                    1.0,
+                    &[1.0, 1.25, 1.5, 2.0],
+                    false,
+                    false,
+                ),
+                mode(
+                    "1920x1080@60.000",
+                    (1920, 1080, 60.0),
+                    1.0,
+                    &[1.0, 1.25, 2.0],
+                    false,
+                    false,
+                ),
+            ],
+            monitor_properties("Dell 27\"", (597, 336), false),
+        ),
+        (
+            spec("HDMI-1"),
+            vec![mode(
+                "1920x1080@60.000",
+                (1920, 1080, 60.0),
+                1.0,
+                &[1.0, 2.0],
+                false,
+                true,
+            )],
+            monitor_properties("Projector", (160, 90), false),
+        ),
+    ];
+
+    let logical_monitors = logical_monitors
+        .iter()
+        .map(|(x, y, scale, transform, primary, connectors)| {
+            (
+                *x,
+                *y,
+                *scale,
+                *transform,
+                *primary,
+                connectors.iter().map(|connector| spec(connector)).collect(),
+                PropMap::new(),
+            )
+        })
+        .collect();
+
+    let mut properties = PropMap::new();
+    properties.insert("supports-mirroring".to_string(), variant(true));
+    properties.insert("layout-mode".to_string(), variant(1u32));
+    properties.insert("supports-changing-layout-mode".to_string(), variant(true));
+
+    DisplayConfig::from((1, monitors, logical_monitors, properties))
+}
+
+/// eDP-1 at scale 2 on the left as the primary monitor, and DP-1 at scale 1 right next to it.
+pub fn laptop_and_monitor() -> DisplayConfig {
+    config(&[
+        (0, 0, 2.0, 0, true, &["eDP-1"]),
+        (1440, 0, 1.0, 0, false, &["DP-1"]),
+    ])
+}
@@ -0,0 +1,399 @@
+use super::{geometry::Rect, ApplyConfig, DisplayConfig, LayoutMode};
+
+/// A reason the display server would reject a proposed configuration. Logical monitors are named by
+/// their connectors, joined with " + " when mirrored.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ValidationError {
+    AllDisabled,
+    NoPrimary,
+    MultiplePrimaries(Vec<String>),
+    UnknownConnector(String),
+    UnknownMode {
+        connector: String,
+        mode_id: String,
+    },
+    UnsupportedScale {
+        connector: String,
+        mode_id: String,
+        scale: f64,
+    },
+    MixedScales(Vec<f64>),
+    Overlap(String, String),
+    Detached(String),
+    TooLarge {
+        width: i32,
+        height: i32,
+        max_width: i32,
+        max_height: i32,
+    },
+}
+
+impl std::fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ValidationError::AllDisabled => write!(f, "every output is disabled"),
+            ValidationError::NoPrimary => write!(f, "no logical monitor is primary"),
+            ValidationError::MultiplePrimaries(names) => {
+                write!(
+                    f,
+                    "more than one logical monitor is primary: {}",
+                    names.join(", ")
+                )
+            }
+            ValidationError::UnknownConnector(connector) => {
+                write!(f, "{} is not a connected monitor", connector)
+            }
+            ValidationError::UnknownMode { connector, mode_id } => {
+                write!(f, "{} has no mode {}", connector, mode_id)
+            }
+            ValidationError::UnsupportedScale {
+                connector,
+                mode_id,
+                scale,
+            } => write!(
+                f,
+                "scale {} is not supported by {} in mode {}",
+                scale, connector, mode_id
+            ),
+            ValidationError::MixedScales(scales) => write!(
+                f,
+                "the display server requires a single scale, but got {}",
+                scales
+                    .iter()
+                    .map(|scale| scale.to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            ValidationError::Overlap(a, b) => write!(f, "{} overlaps {}", a, b),
+            ValidationError::Detached(name) => {
+                write!(f, "{} does not touch the rest of the layout", name)
+            }
+            ValidationError::TooLarge {
+                width,
+                height,
+                max_width,
+                max_height,
+            } => write!(
+                f,
+                "the layout is {}x{}, larger than the maximum screen size of {}x{}",
+                width, height, max_width, max_height
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ValidationError {}
+
+fn name(apply_config: &ApplyConfig) -> String {
+    apply_config
+        .monitors
+        .iter()
+        .map(|monitor| monitor.connector)
+        .collect::<Vec<_>>()
+        .join(" + ")
+}
+
+/// Which rects can be reached from the first one by moving between touching rects. Overlapping rects
+/// count as touching, since they are reported separately.
+fn connected(rects: &[Rect]) -> Vec<bool> {
+    let mut reached = vec![false; rects.len()];
+    let mut pending = Vec::new();
+    if let Some(first) = reached.first_mut() {
+        *first = true;
+        pending.push(0);
+    }
+
+    while let Some(i) = pending.pop() {
+        for (j, rect) in rects.iter().enumerate() {
+            if !reached[j] && (rects[i].touching_edge(rect).is_some() || rects[i].intersects(rect))
+            {
+                reached[j] = true;
+                pending.push(j);
+            }
+        }
+    }
+
+    reached
+}
+
+impl DisplayConfig {
+    /// Checks the given logical monitors for the mistakes the display server would reject them for,
+    /// so they can be reported before calling `apply_monitors_config`. `layout_mode` is the layout
+    /// mode that would be sent along with them, if any. Returns every problem found.
+    pub fn validate_monitors_config(
+        &self,
+        configs: &[ApplyConfig],
+        layout_mode: Option<LayoutMode>,
+    ) -> Vec<ValidationError> {
+        let mut errors = Vec::new();
+
+        if configs
+            .iter()
+            .all(|apply_config| apply_config.monitors.is_empty())
+        {
+            errors.push(ValidationError::AllDisabled);
+            return errors;
+        }
+
+        let primaries: Vec<String> = configs
+            .iter()
+            .filter(|apply_config| apply_config.primary)
+            .map(name)
+            .collect();
+        match primaries.len() {
+            0 => errors.push(ValidationError::NoPrimary),
+            1 => {}
+            _ => errors.push(ValidationError::MultiplePrimaries(primaries)),
+        }
+
+        for apply_config in configs.iter() {
+            for monitor in apply_config.monitors.iter() {
+                let physical_monitor = match self.search_physical(monitor.connector) {
+                    Some(physical_monitor) => physical_monitor,
+                    None => {
+                        errors.push(ValidationError::UnknownConnector(
+                            monitor.connector.to_string(),
+                        ));
+                        continue;
+                    }
+                };
+
+                match physical_monitor
+                    .modes
+                    .iter()
+                    .find(|mode| mode.id == monitor.mode_id)
+                {
+                    Some(mode) if mode.find_scale(apply_config.scale).is_none() => {
+                        errors.push(ValidationError::UnsupportedScale {
+                            connector: monitor.connector.to_string(),
+                            mode_id: monitor.mode_id.to_string(),
+                            scale: apply_config.scale,
+                        })
+                    }
+                    Some(_) => {}
+                    None => errors.push(ValidationError::UnknownMode {
+                        connector: monitor.connector.to_string(),
+                        mode_id: monitor.mode_id.to_string(),
+                    }),
+                }
+            }
+        }
+
+        if self.known_properties.global_scale_required {
+            let mut scales: Vec<f64> = Vec::new();
+            for apply_config in configs.iter() {
+                if !scales.contains(&apply_config.scale) {
+                    scales.push(apply_config.scale);
+                }
+            }
+            if scales.len() > 1 {
+                errors.push(ValidationError::MixedScales(scales));
+            }
+        }
+
+        // Logical monitors whose size can't be worked out have already been reported above.
+        let layout_mode = layout_mode.unwrap_or(self.known_properties.layout_mode);
+        let placed: Vec<(&ApplyConfig, Rect)> = configs
+            .iter()
+            .filter_map(|apply_config| {
+                Some((
+                    apply_config,
+                    apply_config.rect(&self.monitors, layout_mode)?,
+                ))
+            })
+            .collect();
+
+        for (i, (a, a_rect)) in placed.iter().enumerate() {
+            for (b, b_rect) in placed[i + 1..].iter() {
+                if a_rect.intersects(b_rect) {
+                    errors.push(ValidationError::Overlap(name(a), name(b)));
+                }
+            }
+        }
+
+        let rects: Vec<Rect> = placed.iter().map(|(_, rect)| *rect).collect();
+        for ((apply_config, _), reached) in placed.iter().zip(connected(&rects)) {
+            if !reached {
+                errors.push(ValidationError::Detached(name(apply_config)));
+            }
+        }
+
+        let bounds = rects
+            .iter()
+            .copied()
+            .reduce(|bounds, rect| bounds.union(&rect));
+        if let (Some((max_width, max_height)), Some(bounds)) = (self.max_screen_size(), bounds) {
+            if bounds.width > max_width || bounds.height > max_height {
+                errors.push(ValidationError::TooLarge {
+                    width: bounds.width,
+                    height: bounds.height,
+                    max_width,
+                    max_height,
+                });
+            }
+        }
+
+        errors
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::display_config::{testing, ApplyMonitor};
+
+    fn apply_configs(config: &DisplayConfig) -> Vec<ApplyConfig<'_>> {
+        config
+            .logical_monitors
+            .iter()
+            .map(|logical_monitor| ApplyConfig::from(logical_monitor, &config.monitors))
+            .collect()
+    }
+
+    #[test]
+    fn accepts_the_current_layout() {
+        let config = testing::laptop_and_monitor();
+        let configs = apply_configs(&config);
+
+        assert_eq!(config.validate_monitors_config(&configs, None), vec![]);
+    }
+
+    #[test]
+    fn reports_primary_problems() {
+        let config = testing::laptop_and_monitor();
+
+        let mut configs = apply_configs(&config);
+        configs[0].primary = false;
+        assert_eq!(
+            config.validate_monitors_config(&configs, None),
+            vec![ValidationError::NoPrimary]
+        );
+
+        configs[0].primary = true;
+        configs[1].primary = true;
+        assert_eq!(
+            config.validate_monitors_config(&configs, None),
+            vec![ValidationError::MultiplePrimaries(vec![
+                "eDP-1".to_string(),
+                "DP-1".to_string()
+            ])]
+        );
+    }
+
+    #[test]
+    fn reports_unknown_connectors_modes_and_scales() {
+        let config = testing::laptop_and_monitor();
+        let mut configs = apply_configs(&config);
+        configs[1].scale = 1.75;
+        configs[1].monitors.push(ApplyMonitor {
+            connector: "DP-2",
+            mode_id: "1920x1080@60.000",
+        });
+        configs[0].monitors[0].mode_id = "1024x768@60.000";
+
+        assert_eq!(
+            config.validate_monitors_config(&configs, None),
+            vec![
+                ValidationError::UnknownMode {
+                    connector: "eDP-1".to_string(),
+                    mode_id: "1024x768@60.000".to_string(),
+                },
+                ValidationError::UnsupportedScale {
+                    connector: "DP-1".to_string(),
+                    mode_id: "2560x1440@59.951".to_string(),
+                    scale: 1.75,
+                },
+                ValidationError::UnknownConnector("DP-2".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn reports_overlapping_and_detached_monitors() {
+        let config = testing::laptop_and_monitor();
+
+        // At scale 1.5 the laptop is 1920 wide, so it runs into the monitor.
+        let mut configs = apply_configs(&config);
+        configs[0].scale = 1.5;
+        assert_eq!(
+            config.validate_monitors_config(&configs, None),
+            vec![ValidationError::Overlap(
+                "eDP-1".to_string(),
+                "DP-1".to_string()
+            )]
+        );
+
+        let mut configs = apply_configs(&config);
+        configs[1].x_pos = 1500;
+        assert_eq!(
+            config.validate_monitors_config(&configs, None),
+            vec![ValidationError::Detached("DP-1".to_string())]
+        );
+
+        // In the physical layout mode the laptop is 2880 wide.
+        let configs = apply_configs(&config);
+        assert_eq!(
+            config.validate_monitors_config(&configs, Some(LayoutMode::Physical)),
+            vec![ValidationError::Overlap(
+                "eDP-1".to_string(),
+                "DP-1".to_string()
+            )]
+        );
+    }
+
+    #[test]
+    fn limits_the_screen_size_per_axis() {
+        let mut config = testing::laptop_and_monitor();
+        config.monitors[0].known_properties.max_screen_size = Some((8192, 1000));
+        config.monitors[1].known_properties.max_screen_size = Some((3000, 8192));
+
+        let configs = apply_configs(&config);
+        assert_eq!(
+            config.validate_monitors_config(&configs, None),
+            vec![ValidationError::TooLarge {
+                width: 4000,
+                height: 1440,
+                max_width: 3000,
+                max_height: 1000,
+            }]
+        );
+    }
+
+    #[test]
+    fn requires_a_global_scale_if_the_server_does() {
+        let mut config = testing::laptop_and_monitor();
+        config.known_properties.global_scale_required = true;
+
+        let configs = apply_configs(&config);
+        assert_eq!(
+            config.validate_monitors_config(&configs, None),
+            vec![ValidationError::MixedScales(vec![2.0, 1.0])]
+        );
+    }
+
+    #[test]
+    fn reports_empty_layouts() {
+        let config = testing::laptop_and_monitor();
+
+        assert_eq!(
+            config.validate_monitors_config(&[], None),
+            vec![ValidationError::AllDisabled]
+        );
+
+        let configs = vec![ApplyConfig {
+            x_pos: 0,
+            y_pos: 0,
+            scale: 1.0,
+            transform: 0,
+            primary: true,
+            monitors: vec![ApplyMonitor {
+                connector: "DP-2",
+                mode_id: "1920x1080@60.000",
+            }],
+        }];
+        assert_eq!(
+            config.validate_monitors_config(&configs, None),
+            vec![ValidationError::UnknownConnector("DP-2".to_string())]
+        );
+    }
+}